@@ -1,7 +1,7 @@
 use num_derive::FromPrimitive;
 
 use crate::{
-    data::{BaseItemData, ItemReference, NpcId, SkillAddAbility, SkillData},
+    data::{item::Item, BaseItemData, ItemReference, NpcId, SkillAddAbility, SkillData},
     game::components::{
         AbilityValues, BasicStatType, BasicStats, CharacterInfo, Equipment, Level, SkillList,
         StatusEffects,
@@ -219,6 +219,14 @@ pub trait AbilityValueCalculator {
         world_prices_rate: i32,
     ) -> i32;
 
+    fn calculate_npc_store_item_sell_price(
+        &self,
+        item: &Item,
+        item_data: &BaseItemData,
+        sell_skill_value: i32,
+        world_prices_rate: i32,
+    ) -> i32;
+
     fn calculate_passive_recover_hp(
         &self,
         ability_values: &AbilityValues,
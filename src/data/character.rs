@@ -3,9 +3,9 @@ use std::{io::Write, path::PathBuf};
 
 use crate::data::CHARACTER_STORAGE_DIR;
 use crate::game::components::{
-    BasicStats, CharacterDeleteTime, CharacterInfo, Equipment, ExperiencePoints, HealthPoints,
-    Hotbar, Inventory, Level, ManaPoints, Position, QuestState, SkillList, SkillPoints, Stamina,
-    StatPoints, UnionMembership,
+    BasicStats, CharacterDeleteTime, CharacterInfo, Equipment, ExperiencePoints, FriendList,
+    HealthPoints, Hotbar, Inventory, Level, Mailbox, ManaPoints, Position, QuestState,
+    SavedCooldowns, SkillList, SkillPoints, Stamina, StatPoints, UnionMembership,
 };
 
 #[derive(Debug)]
@@ -55,6 +55,12 @@ pub struct CharacterStorage {
     pub quest_state: QuestState,
     pub union_membership: UnionMembership,
     pub stamina: Stamina,
+    #[serde(default)]
+    pub friend_list: FriendList,
+    #[serde(default)]
+    pub mailbox: Mailbox,
+    #[serde(default)]
+    pub cooldowns: SavedCooldowns,
 }
 
 fn get_character_path(name: &str) -> PathBuf {
@@ -1,5 +1,6 @@
 use log::warn;
 use std::{
+    fmt,
     num::NonZeroU8,
     ops::{Range, RangeInclusive},
     time::Duration,
@@ -781,19 +782,23 @@ impl AipFile {
                         12 => {
                             let distance = reader.read_i32()?;
                             let count = reader.read_i32()? as usize;
-                            actions
-                                .push(AipAction::NearbyAlliesAttackTarget(count, distance, None));
+                            actions.push(AipAction::NearbyAlliesAttackTarget(
+                                count,
+                                distance * 100,
+                                None,
+                            ));
                         }
                         13 => actions.push(AipAction::AttackNearChar),
                         14 => actions.push(AipAction::AttackFindChar),
                         15 => {
                             let distance = reader.read_i32()?;
-                            actions.push(AipAction::NearbyAlliesSameNpcAttackTarget(distance));
+                            actions
+                                .push(AipAction::NearbyAlliesSameNpcAttackTarget(distance * 100));
                         }
                         16 => actions.push(AipAction::AttackAttacker),
                         17 => {
                             let distance = reader.read_i32()?;
-                            actions.push(AipAction::RunAway(distance));
+                            actions.push(AipAction::RunAway(distance * 100));
                         }
                         18 => {
                             let mut items = Vec::new();
@@ -814,7 +819,7 @@ impl AipFile {
 
                             actions.push(AipAction::NearbyAlliesAttackTarget(
                                 count,
-                                distance,
+                                distance * 100,
                                 Some(npc_id),
                             ));
                         }
@@ -830,7 +835,12 @@ impl AipFile {
                             reader.skip(1); // padding
                             let distance = reader.read_i32()?;
 
-                            actions.push(AipAction::SpawnNpc(npc_id, distance, position, false));
+                            actions.push(AipAction::SpawnNpc(
+                                npc_id,
+                                distance * 100,
+                                position,
+                                false,
+                            ));
                         }
                         22 => { /* no-op */ }
                         23 => { /* no-op */ }
@@ -983,7 +993,12 @@ impl AipFile {
                             let is_owner = reader.read_u8()? != 0;
                             reader.skip(3); // padding
 
-                            actions.push(AipAction::SpawnNpc(npc_id, distance, position, is_owner));
+                            actions.push(AipAction::SpawnNpc(
+                                npc_id,
+                                distance * 100,
+                                position,
+                                is_owner,
+                            ));
                         }
                         _ => {
                             warn!("Unimplemented AIP action opcode: {:#X}", opcode);
@@ -1023,3 +1038,60 @@ impl AipFile {
         })
     }
 }
+
+impl fmt::Display for AipTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for event in self.events.iter() {
+            writeln!(f, "  event \"{}\"", event.name)?;
+
+            if event.conditions.is_empty() {
+                writeln!(f, "    always")?;
+            }
+
+            for (index, condition) in event.conditions.iter().enumerate() {
+                if index == 0 {
+                    writeln!(f, "    if {:?}", condition)?;
+                } else {
+                    writeln!(f, "    and {:?}", condition)?;
+                }
+            }
+
+            writeln!(f, "    then")?;
+            for action in event.actions.iter() {
+                writeln!(f, "      {:?}", action)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for AipFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "idle_trigger_interval: {:?}", self.idle_trigger_interval)?;
+        writeln!(
+            f,
+            "damage_trigger_new_target_chance: {}",
+            self.damage_trigger_new_target_chance
+        )?;
+
+        let triggers = [
+            ("on_created", &self.trigger_on_created),
+            ("on_idle", &self.trigger_on_idle),
+            ("on_attack_move", &self.trigger_on_attack_move),
+            ("on_damaged", &self.trigger_on_damaged),
+            ("on_kill", &self.trigger_on_kill),
+            ("on_dead", &self.trigger_on_dead),
+        ];
+
+        for (trigger_type, trigger) in triggers.iter() {
+            if let Some(trigger) = trigger {
+                writeln!(f)?;
+                writeln!(f, "trigger {} \"{}\"", trigger_type, trigger.name)?;
+                write!(f, "{}", trigger)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use log::warn;
 use nalgebra::Point2;
 use num_traits::FromPrimitive;
-use std::{collections::HashMap, num::NonZeroU8, ops::RangeInclusive, time::Duration};
+use std::{collections::HashMap, fmt, num::NonZeroU8, ops::RangeInclusive, time::Duration};
 
 use crate::{
     data::{
@@ -1111,3 +1111,32 @@ impl QsdFile {
         Ok(QsdFile { triggers })
     }
 }
+
+impl fmt::Display for QsdTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "trigger \"{}\"", self.name)?;
+
+        if self.conditions.is_empty() {
+            writeln!(f, "  always")?;
+        }
+
+        for (index, condition) in self.conditions.iter().enumerate() {
+            if index == 0 {
+                writeln!(f, "  if {:?}", condition)?;
+            } else {
+                writeln!(f, "  and {:?}", condition)?;
+            }
+        }
+
+        writeln!(f, "  then")?;
+        for reward in self.rewards.iter() {
+            writeln!(f, "    {:?}", reward)?;
+        }
+
+        if let Some(next_trigger_name) = self.next_trigger_name.as_ref() {
+            writeln!(f, "  else \"{}\"", next_trigger_name)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use std::{collections::HashMap, num::NonZeroUsize, str::FromStr};
+
+use arrayvec::ArrayVec;
+
+#[derive(Copy, Clone, Debug)]
+pub struct JobClassId(NonZeroUsize);
+
+id_wrapper_impl!(JobClassId, NonZeroUsize, usize);
+
+#[derive(Debug)]
+pub struct JobClassData {
+    pub id: JobClassId,
+    pub jobs: ArrayVec<u16, 8>,
+}
+
+impl JobClassData {
+    pub fn contains_job(&self, job: u16) -> bool {
+        self.jobs.contains(&job)
+    }
+}
+
+pub struct JobClassDatabase {
+    job_classes: HashMap<usize, JobClassData>,
+}
+
+impl JobClassDatabase {
+    pub fn new(job_classes: HashMap<usize, JobClassData>) -> Self {
+        Self { job_classes }
+    }
+
+    pub fn get_job_class(&self, id: JobClassId) -> Option<&JobClassData> {
+        self.job_classes.get(&id.get())
+    }
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use std::{io::Write, path::PathBuf};
+
+use crate::data::MAIL_STORAGE_DIR;
+use crate::game::components::Mail;
+
+#[derive(Debug)]
+pub enum MailStorageError {
+    IoError,
+}
+
+impl From<std::io::Error> for MailStorageError {
+    fn from(_: std::io::Error) -> Self {
+        MailStorageError::IoError
+    }
+}
+
+impl From<serde_json::Error> for MailStorageError {
+    fn from(_: serde_json::Error) -> Self {
+        MailStorageError::IoError
+    }
+}
+
+impl From<tempfile::PersistError> for MailStorageError {
+    fn from(_: tempfile::PersistError) -> Self {
+        MailStorageError::IoError
+    }
+}
+
+// Mail sent to a character while they are offline, it is kept separate from the
+// character storage so it is never overwritten by a save of the character.
+#[derive(Default, Deserialize, Serialize)]
+pub struct MailStorage {
+    pub mail: Vec<Mail>,
+}
+
+fn get_mail_path(name: &str) -> PathBuf {
+    MAIL_STORAGE_DIR.join(format!("{}.json", name))
+}
+
+impl MailStorage {
+    pub fn try_load(name: &str) -> Result<Self, MailStorageError> {
+        let path = get_mail_path(name);
+        if path.exists() {
+            let str = std::fs::read_to_string(path)?;
+            let mail_storage: Self = serde_json::from_str(&str)?;
+            Ok(mail_storage)
+        } else {
+            Ok(Default::default())
+        }
+    }
+
+    pub fn save(&self, name: &str) -> Result<(), MailStorageError> {
+        let path = get_mail_path(name);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+
+        let json = serde_json::to_string_pretty(self)?;
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all(json.as_bytes())?;
+        file.persist(path)?;
+        Ok(())
+    }
+
+    pub fn delete(name: &str) -> Result<(), MailStorageError> {
+        let path = get_mail_path(name);
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
@@ -9,6 +9,7 @@ lazy_static! {
     };
     pub static ref ACCOUNT_STORAGE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("accounts");
     pub static ref CHARACTER_STORAGE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("characters");
+    pub static ref MAIL_STORAGE_DIR: PathBuf = LOCAL_STORAGE_DIR.join("mail");
 }
 
 macro_rules! id_wrapper_impl {
@@ -70,6 +71,7 @@ mod ability;
 mod ai_database;
 mod drop_table;
 mod item_database;
+mod job_class_database;
 mod motion_database;
 mod npc_database;
 mod quest_database;
@@ -82,6 +84,7 @@ pub mod account;
 pub mod character;
 pub mod formats;
 pub mod item;
+pub mod mail;
 
 pub use ability::{AbilityType, AbilityValueCalculator, Damage, PassiveRecoveryState};
 pub use ai_database::AiDatabase;
@@ -93,6 +96,7 @@ pub use item_database::{
     JewelleryItemData, MaterialItemData, QuestItemData, SubWeaponItemData, VehicleItemData,
     WeaponItemData,
 };
+pub use job_class_database::{JobClassData, JobClassDatabase, JobClassId};
 pub use motion_database::{MotionCharacterAction, MotionDatabase, MotionFileData, MotionId};
 pub use npc_database::{
     NpcConversationData, NpcConversationId, NpcData, NpcDatabase, NpcId, NpcMotionAction,
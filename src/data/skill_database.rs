@@ -8,7 +8,9 @@ use std::{
     time::Duration,
 };
 
-use crate::data::{item::ItemClass, AbilityType, MotionId, NpcId, StatusEffectId, ZoneId};
+use crate::data::{
+    item::ItemClass, AbilityType, JobClassId, MotionId, NpcId, StatusEffectId, ZoneId,
+};
 
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, Hash, PartialEq, Eq)]
 pub struct SkillId(NonZeroU16);
@@ -129,7 +131,7 @@ pub struct SkillData {
 
     pub use_ability: ArrayVec<(AbilityType, i32), 2>,
     pub required_ability: ArrayVec<(AbilityType, i32), 2>,
-    pub required_job_set_index: Option<NonZeroUsize>, // TODO: JobSetReference to the job set STB
+    pub required_job_class: Option<JobClassId>,
     pub required_planet: Option<NonZeroUsize>,
     pub required_skills: ArrayVec<(SkillId, i32), 3>,
     pub required_union: ArrayVec<NonZeroUsize, 3>,
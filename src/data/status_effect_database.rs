@@ -9,7 +9,7 @@ pub struct StatusEffectId(NonZeroU16);
 
 id_wrapper_impl!(StatusEffectId, NonZeroU16, u16);
 
-#[derive(Copy, Clone, Debug, Enum, FromPrimitive)]
+#[derive(Copy, Clone, Debug, PartialEq, Enum, FromPrimitive)]
 pub enum StatusEffectType {
     IncreaseHp = 1,
     IncreaseMp = 2,
@@ -87,6 +87,16 @@ impl StatusEffectType {
                 | StatusEffectType::AdditionalDamageRate
         )
     }
+
+    pub fn is_per_tick(&self) -> bool {
+        matches!(
+            *self,
+            StatusEffectType::IncreaseHp
+                | StatusEffectType::IncreaseMp
+                | StatusEffectType::Poisoned
+                | StatusEffectType::DecreaseLifeTime
+        )
+    }
 }
 
 #[derive(FromPrimitive)]
@@ -107,6 +117,7 @@ pub struct StatusEffectData {
     pub status_effect_type: StatusEffectType,
     pub can_be_reapplied: bool,
     pub cleared_by_type: StatusEffectClearedByType,
+    pub apply_per_second_value: i32,
     pub apply_status_effects: ArrayVec<(StatusEffectId, i32), 2>,
 }
 
@@ -122,4 +133,13 @@ impl StatusEffectDatabase {
     pub fn get_status_effect(&self, id: StatusEffectId) -> Option<&StatusEffectData> {
         self.status_effects.get(&(id.get() as u16))
     }
+
+    pub fn find_status_effect_by_type(
+        &self,
+        status_effect_type: StatusEffectType,
+    ) -> Option<&StatusEffectData> {
+        self.status_effects
+            .values()
+            .find(|status_effect_data| status_effect_data.status_effect_type == status_effect_type)
+    }
 }
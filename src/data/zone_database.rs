@@ -57,6 +57,7 @@ pub struct ZoneData {
     pub day_time: u32,
     pub evening_time: u32,
     pub night_time: u32,
+    pub planet: u32,
 }
 
 impl ZoneData {
@@ -8,12 +8,12 @@ use crate::{
     game::{
         components::{
             AbilityValues, BasicStats, CharacterInfo, ClientEntity, ClientEntityId,
-            ClientEntityType, ClientEntityVisibility, Command, DamageSources, DroppedItem,
-            Equipment, ExperiencePoints, SpawnExpireTime, GameClient, HealthPoints, Hotbar, Inventory,
-            Level, ManaPoints, MotionData, MoveMode, MoveSpeed, NextCommand, Npc, NpcAi,
-            NpcStandingDirection, ObjectVariables, Owner, PassiveRecoveryTime, Position,
-            QuestState, SkillList, SkillPoints, SpawnOrigin, Stamina, StatPoints, StatusEffects,
-            Team, UnionMembership,
+            ClientEntityType, ClientEntityVisibility, Command, Cooldowns, DamageSources,
+            DroppedItem, Equipment, ExperiencePoints, FriendList, GameClient, HealthPoints, Hotbar,
+            Inventory, Level, Mailbox, ManaPoints, MotionData, MoveMode, MoveSpeed, NextCommand,
+            Npc, NpcAi, NpcStandingDirection, ObjectVariables, Owner, PassiveRecoveryTime,
+            Position, QuestState, SkillList, SkillPoints, SpawnExpireTime, SpawnOrigin, Stamina,
+            StatPoints, StatusEffects, Team, UnionMembership,
         },
         messages::server::{ServerMessage, Teleport},
         resources::{ClientEntityList, ServerTime},
@@ -51,13 +51,16 @@ pub struct CharacterBundle {
     pub ability_values: AbilityValues,
     pub basic_stats: BasicStats,
     pub command: Command,
+    pub cooldowns: Cooldowns,
     pub equipment: Equipment,
     pub experience_points: ExperiencePoints,
+    pub friend_list: FriendList,
     pub health_points: HealthPoints,
     pub hotbar: Hotbar,
     pub info: CharacterInfo,
     pub inventory: Inventory,
     pub level: Level,
+    pub mailbox: Mailbox,
     pub mana_points: ManaPoints,
     pub motion_data: MotionData,
     pub move_mode: MoveMode,
@@ -136,10 +139,11 @@ impl MonsterBundle {
             SpawnOrigin::Quest(_, spawn_position) => spawn_position,
         };
 
+        let spawn_range = spawn_range.max(0);
         let position = Position::new(
             Point3::new(
-                spawn_position.x + rand::thread_rng().gen_range(-spawn_range..spawn_range) as f32,
-                spawn_position.y + rand::thread_rng().gen_range(-spawn_range..spawn_range) as f32,
+                spawn_position.x + rand::thread_rng().gen_range(-spawn_range..=spawn_range) as f32,
+                spawn_position.y + rand::thread_rng().gen_range(-spawn_range..=spawn_range) as f32,
                 0.0,
             ),
             spawn_zone,
@@ -0,0 +1,20 @@
+use crate::{
+    data::AbilityValueCalculator,
+    game::components::{ExperiencePoints, Level},
+};
+
+pub fn experience_points_apply_penalty(
+    ability_value_calculator: &dyn AbilityValueCalculator,
+    level: &Level,
+    experience_points: &mut ExperiencePoints,
+    penalty_percent: u32,
+) -> u64 {
+    let penalty_xp = ability_value_calculator.calculate_levelup_require_xp(level.level)
+        * penalty_percent as u64
+        / 100;
+
+    // A penalty can not take a character below zero xp for their level
+    let penalty_xp = penalty_xp.min(experience_points.xp);
+    experience_points.xp -= penalty_xp;
+    penalty_xp
+}
@@ -1,5 +1,6 @@
 mod ability_values;
 mod entity;
+mod experience_points;
 mod skills;
 
 pub use ability_values::{
@@ -10,4 +11,5 @@ pub use entity::{
     CharacterBundle, DroppedItemBundle, MonsterBundle, NpcBundle, EVENT_OBJECT_VARIABLES_COUNT,
     MONSTER_OBJECT_VARIABLES_COUNT, NPC_OBJECT_VARIABLES_COUNT,
 };
-pub use skills::skill_list_try_learn_skill;
+pub use experience_points::experience_points_apply_penalty;
+pub use skills::{skill_list_try_learn_skill, SkillListLearnRequirements};
@@ -1,20 +1,152 @@
 use bevy_ecs::prelude::Mut;
 
 use crate::{
-    data::{SkillDatabase, SkillId},
+    data::{AbilityType, SkillData, SkillDatabase, SkillId},
     game::{
-        components::{GameClient, SkillList, SkillPoints, SkillSlot},
+        bundles::ability_values_get_value,
+        components::{
+            AbilityValues, CharacterInfo, ExperiencePoints, GameClient, Inventory, Level,
+            MoveSpeed, Position, SkillList, SkillPoints, SkillSlot, Stamina, StatPoints, Team,
+            UnionMembership,
+        },
         messages::server::{LearnSkillError, LearnSkillSuccess, ServerMessage},
+        GameData,
     },
 };
 
-fn try_learn_skill(
+pub struct SkillListLearnRequirements<'a> {
+    pub ability_values: &'a AbilityValues,
+    pub level: &'a Level,
+    pub move_speed: &'a MoveSpeed,
+    pub position: &'a Position,
+    pub team: &'a Team,
+    pub character_info: Option<&'a CharacterInfo>,
+    pub experience_points: Option<&'a ExperiencePoints>,
+    pub inventory: Option<&'a Inventory>,
+    pub stamina: Option<&'a Stamina>,
+    pub stat_points: Option<&'a StatPoints>,
+    pub union_membership: Option<&'a UnionMembership>,
+}
+
+fn has_required_skill(
     skill_database: &SkillDatabase,
+    skill_list: &SkillList,
+    required_skill_id: SkillId,
+    required_level: i32,
+) -> bool {
+    let required_skill_data = match skill_database.get_skill(required_skill_id) {
+        Some(skill_data) => skill_data,
+        None => return false,
+    };
+    let required_base_skill_id = required_skill_data
+        .base_skill_id
+        .unwrap_or(required_skill_data.id);
+
+    // Any learnt level of the required skill which meets the level requirement
+    skill_list
+        .iter_skills()
+        .filter_map(|skill_id| skill_database.get_skill(*skill_id))
+        .any(|skill_data| {
+            skill_data.base_skill_id.unwrap_or(skill_data.id) == required_base_skill_id
+                && skill_data.level as i32 >= required_level
+        })
+}
+
+fn check_skill_requirements(
+    game_data: &GameData,
+    skill_data: &SkillData,
+    skill_list: &SkillList,
+    skill_points: Option<&SkillPoints>,
+    requirements: &SkillListLearnRequirements,
+) -> Result<(), LearnSkillError> {
+    if let Some(job_class_id) = skill_data.required_job_class {
+        let job = requirements
+            .character_info
+            .map(|character_info| character_info.job)
+            .ok_or(LearnSkillError::JobRequirement)?;
+
+        if !game_data
+            .job_classes
+            .get_job_class(job_class_id)
+            .map_or(false, |job_class| job_class.contains_job(job))
+        {
+            return Err(LearnSkillError::JobRequirement);
+        }
+    }
+
+    for &(required_skill_id, required_level) in skill_data.required_skills.iter() {
+        if !has_required_skill(
+            &game_data.skills,
+            skill_list,
+            required_skill_id,
+            required_level,
+        ) {
+            return Err(LearnSkillError::SkillRequirement);
+        }
+    }
+
+    let current_planet = game_data
+        .zones
+        .get_zone(requirements.position.zone_id)
+        .map_or(0, |zone_data| zone_data.planet as i32);
+
+    for &(ability_type, required_value) in skill_data.required_ability.iter() {
+        // Current planet is not an ability value, so we must compare with the zone planet
+        let ability_value = if matches!(ability_type, AbilityType::CurrentPlanet) {
+            current_planet
+        } else {
+            ability_values_get_value(
+                ability_type,
+                requirements.ability_values,
+                requirements.level,
+                requirements.move_speed,
+                requirements.team,
+                requirements.character_info,
+                requirements.experience_points,
+                requirements.inventory,
+                skill_points,
+                requirements.stamina,
+                requirements.stat_points,
+                requirements.union_membership,
+            )
+            .unwrap_or(0)
+        };
+
+        if ability_value < required_value {
+            return Err(LearnSkillError::AbilityRequirement);
+        }
+    }
+
+    if !skill_data.required_union.is_empty() {
+        let current_union = requirements
+            .union_membership
+            .and_then(|union_membership| union_membership.current_union);
+
+        if !current_union.map_or(false, |current_union| {
+            skill_data.required_union.contains(&current_union)
+        }) {
+            return Err(LearnSkillError::UnionRequirement);
+        }
+    }
+
+    if let Some(required_planet) = skill_data.required_planet {
+        if current_planet as usize != required_planet.get() {
+            return Err(LearnSkillError::PlanetRequirement);
+        }
+    }
+
+    Ok(())
+}
+
+fn try_learn_skill(
+    game_data: &GameData,
     skill_id: SkillId,
     skill_list: &mut SkillList,
     skill_points: Option<&mut Mut<SkillPoints>>,
+    requirements: &SkillListLearnRequirements,
 ) -> Result<SkillSlot, LearnSkillError> {
-    let skill_data = skill_database
+    let skill_data = game_data
+        .skills
         .get_skill(skill_id)
         .ok_or(LearnSkillError::InvalidSkillId)?;
 
@@ -28,7 +160,13 @@ fn try_learn_skill(
         }
     }
 
-    // TODO: Check skill job / skill / ability requirements
+    check_skill_requirements(
+        game_data,
+        skill_data,
+        skill_list,
+        skill_points.as_deref().map(|skill_points| &**skill_points),
+        requirements,
+    )?;
 
     let (skill_slot, _) = skill_list
         .add_skill(skill_data)
@@ -42,17 +180,19 @@ fn try_learn_skill(
 }
 
 pub fn skill_list_try_learn_skill(
-    skill_database: &SkillDatabase,
+    game_data: &GameData,
     skill_id: SkillId,
     skill_list: &mut SkillList,
     mut skill_points: Option<&mut Mut<SkillPoints>>,
+    requirements: &SkillListLearnRequirements,
     game_client: Option<&GameClient>,
 ) -> Result<SkillSlot, LearnSkillError> {
     let result = try_learn_skill(
-        skill_database,
+        game_data,
         skill_id,
         skill_list,
         skill_points.as_deref_mut(),
+        requirements,
     );
 
     if let Some(game_client) = game_client {
@@ -79,3 +219,308 @@ pub fn skill_list_try_learn_skill(
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, num::NonZeroUsize, sync::Arc};
+
+    use arrayvec::ArrayVec;
+    use nalgebra::Point3;
+
+    use super::*;
+    use crate::{
+        data::{JobClassData, JobClassDatabase, JobClassId, ZoneDatabase},
+        game::test_utils::{
+            test_ability_values, test_game_data, test_skill_data, test_zone_data, test_zone_id,
+        },
+    };
+
+    const TEST_JOB_CLASS_ID: usize = 1;
+
+    struct TestCharacter {
+        ability_values: AbilityValues,
+        level: Level,
+        move_speed: MoveSpeed,
+        position: Position,
+        team: Team,
+        character_info: CharacterInfo,
+        union_membership: UnionMembership,
+    }
+
+    impl TestCharacter {
+        fn new(job: u16) -> Self {
+            Self {
+                ability_values: test_ability_values(),
+                level: Level::new(10),
+                move_speed: MoveSpeed::new(400.0),
+                position: Position::new(Point3::new(0.0, 0.0, 0.0), test_zone_id()),
+                team: Team::default_character(),
+                character_info: CharacterInfo {
+                    name: String::from("Test Character"),
+                    gender: 0,
+                    birth_stone: 0,
+                    job,
+                    face: 0,
+                    hair: 0,
+                    rank: 0,
+                    fame: 0,
+                    fame_b: 0,
+                    fame_g: 0,
+                    revive_zone_id: test_zone_id(),
+                    revive_position: Point3::new(0.0, 0.0, 0.0),
+                },
+                union_membership: UnionMembership::new(),
+            }
+        }
+
+        fn requirements(&self) -> SkillListLearnRequirements {
+            SkillListLearnRequirements {
+                ability_values: &self.ability_values,
+                level: &self.level,
+                move_speed: &self.move_speed,
+                position: &self.position,
+                team: &self.team,
+                character_info: Some(&self.character_info),
+                experience_points: None,
+                inventory: None,
+                stamina: None,
+                stat_points: None,
+                union_membership: Some(&self.union_membership),
+            }
+        }
+    }
+
+    fn skill_id(id: u16) -> SkillId {
+        SkillId::new(id).unwrap()
+    }
+
+    // Skill 1 is the base skill with levels 2 and 3 as skills 2 and 3
+    fn base_skill_levels() -> Vec<SkillData> {
+        (1..=3)
+            .map(|level| {
+                let mut skill_data = test_skill_data(skill_id(level));
+                skill_data.level = level as u32;
+                if level > 1 {
+                    skill_data.base_skill_id = Some(skill_id(1));
+                }
+                skill_data
+            })
+            .collect()
+    }
+
+    fn create_game_data(skills: Vec<SkillData>, planet: u32) -> GameData {
+        let mut job_classes = HashMap::new();
+        job_classes.insert(
+            TEST_JOB_CLASS_ID,
+            JobClassData {
+                id: JobClassId::new(TEST_JOB_CLASS_ID).unwrap(),
+                jobs: [111, 121].iter().copied().collect(),
+            },
+        );
+
+        let mut zones = HashMap::new();
+        zones.insert(test_zone_id(), test_zone_data(planet));
+
+        let mut game_data = test_game_data();
+        game_data.job_classes = Arc::new(JobClassDatabase::new(job_classes));
+        game_data.skills = Arc::new(SkillDatabase::new(
+            skills
+                .into_iter()
+                .map(|skill_data| (skill_data.id.get(), skill_data))
+                .collect(),
+        ));
+        game_data.zones = Arc::new(ZoneDatabase::new(zones));
+        game_data
+    }
+
+    fn check_requirements(
+        game_data: &GameData,
+        skill_id: SkillId,
+        skill_list: &SkillList,
+        character: &TestCharacter,
+    ) -> Result<(), LearnSkillError> {
+        check_skill_requirements(
+            game_data,
+            game_data.skills.get_skill(skill_id).unwrap(),
+            skill_list,
+            None,
+            &character.requirements(),
+        )
+    }
+
+    #[test]
+    fn job_class_requirement() {
+        let mut skill_data = test_skill_data(skill_id(10));
+        skill_data.required_job_class = JobClassId::new(TEST_JOB_CLASS_ID);
+        let mut missing_job_class_skill_data = test_skill_data(skill_id(11));
+        missing_job_class_skill_data.required_job_class = JobClassId::new(2);
+        let game_data = create_game_data(vec![skill_data, missing_job_class_skill_data], 1);
+        let skill_list = SkillList::new();
+
+        for &job in [111, 121].iter() {
+            let character = TestCharacter::new(job);
+            assert!(check_requirements(&game_data, skill_id(10), &skill_list, &character).is_ok());
+
+            // Job class which does not exist in the job class database
+            assert!(matches!(
+                check_requirements(&game_data, skill_id(11), &skill_list, &character),
+                Err(LearnSkillError::JobRequirement)
+            ));
+        }
+
+        let character = TestCharacter::new(211);
+        assert!(matches!(
+            check_requirements(&game_data, skill_id(10), &skill_list, &character),
+            Err(LearnSkillError::JobRequirement)
+        ));
+    }
+
+    #[test]
+    fn skill_requirement() {
+        let mut skills = base_skill_levels();
+        let mut skill_data = test_skill_data(skill_id(10));
+        skill_data.required_skills.push((skill_id(1), 2));
+        skills.push(skill_data);
+        let game_data = create_game_data(skills, 1);
+        let character = TestCharacter::new(111);
+
+        let skill_list = SkillList::new();
+        assert!(matches!(
+            check_requirements(&game_data, skill_id(10), &skill_list, &character),
+            Err(LearnSkillError::SkillRequirement)
+        ));
+
+        // Level 1 of the required skill is not enough
+        let mut skill_list = SkillList::new();
+        skill_list.add_skill(game_data.skills.get_skill(skill_id(1)).unwrap());
+        assert!(matches!(
+            check_requirements(&game_data, skill_id(10), &skill_list, &character),
+            Err(LearnSkillError::SkillRequirement)
+        ));
+
+        // Levels 2 and 3 have a different skill id but share the same base skill
+        for learnt_skill_id in [skill_id(2), skill_id(3)].iter() {
+            let mut skill_list = SkillList::new();
+            skill_list.add_skill(game_data.skills.get_skill(*learnt_skill_id).unwrap());
+            assert!(check_requirements(&game_data, skill_id(10), &skill_list, &character).is_ok());
+        }
+    }
+
+    #[test]
+    fn skill_requirement_by_higher_level_skill_id() {
+        let mut skills = base_skill_levels();
+        let mut skill_data = test_skill_data(skill_id(10));
+        skill_data.required_skills.push((skill_id(3), 3));
+        skills.push(skill_data);
+        let game_data = create_game_data(skills, 1);
+        let character = TestCharacter::new(111);
+
+        let mut skill_list = SkillList::new();
+        skill_list.add_skill(game_data.skills.get_skill(skill_id(2)).unwrap());
+        assert!(matches!(
+            check_requirements(&game_data, skill_id(10), &skill_list, &character),
+            Err(LearnSkillError::SkillRequirement)
+        ));
+
+        let mut skill_list = SkillList::new();
+        skill_list.add_skill(game_data.skills.get_skill(skill_id(3)).unwrap());
+        assert!(check_requirements(&game_data, skill_id(10), &skill_list, &character).is_ok());
+    }
+
+    #[test]
+    fn ability_requirement() {
+        let mut strength_skill_data = test_skill_data(skill_id(10));
+        strength_skill_data
+            .required_ability
+            .push((AbilityType::Strength, 15));
+        let mut level_skill_data = test_skill_data(skill_id(11));
+        level_skill_data
+            .required_ability
+            .push((AbilityType::Level, 11));
+        let game_data = create_game_data(vec![strength_skill_data, level_skill_data], 1);
+        let skill_list = SkillList::new();
+
+        let mut character = TestCharacter::new(111);
+        assert!(check_requirements(&game_data, skill_id(10), &skill_list, &character).is_ok());
+        assert!(matches!(
+            check_requirements(&game_data, skill_id(11), &skill_list, &character),
+            Err(LearnSkillError::AbilityRequirement)
+        ));
+
+        character.ability_values.strength = 14;
+        character.level = Level::new(11);
+        assert!(matches!(
+            check_requirements(&game_data, skill_id(10), &skill_list, &character),
+            Err(LearnSkillError::AbilityRequirement)
+        ));
+        assert!(check_requirements(&game_data, skill_id(11), &skill_list, &character).is_ok());
+    }
+
+    #[test]
+    fn ability_requirement_current_planet() {
+        let create_skill_data = || {
+            let mut skill_data = test_skill_data(skill_id(10));
+            skill_data
+                .required_ability
+                .push((AbilityType::CurrentPlanet, 2));
+            skill_data
+        };
+        let skill_list = SkillList::new();
+        let character = TestCharacter::new(111);
+
+        let game_data = create_game_data(vec![create_skill_data()], 1);
+        assert!(matches!(
+            check_requirements(&game_data, skill_id(10), &skill_list, &character),
+            Err(LearnSkillError::AbilityRequirement)
+        ));
+
+        let game_data = create_game_data(vec![create_skill_data()], 2);
+        assert!(check_requirements(&game_data, skill_id(10), &skill_list, &character).is_ok());
+    }
+
+    #[test]
+    fn union_requirement() {
+        let mut skill_data = test_skill_data(skill_id(10));
+        skill_data.required_union = [NonZeroUsize::new(3).unwrap(), NonZeroUsize::new(5).unwrap()]
+            .iter()
+            .copied()
+            .collect::<ArrayVec<_, 3>>();
+        let game_data = create_game_data(vec![skill_data], 1);
+        let skill_list = SkillList::new();
+
+        let mut character = TestCharacter::new(111);
+        assert!(matches!(
+            check_requirements(&game_data, skill_id(10), &skill_list, &character),
+            Err(LearnSkillError::UnionRequirement)
+        ));
+
+        character.union_membership.current_union = NonZeroUsize::new(2);
+        assert!(matches!(
+            check_requirements(&game_data, skill_id(10), &skill_list, &character),
+            Err(LearnSkillError::UnionRequirement)
+        ));
+
+        character.union_membership.current_union = NonZeroUsize::new(5);
+        assert!(check_requirements(&game_data, skill_id(10), &skill_list, &character).is_ok());
+    }
+
+    #[test]
+    fn planet_requirement() {
+        let create_skill_data = || {
+            let mut skill_data = test_skill_data(skill_id(10));
+            skill_data.required_planet = NonZeroUsize::new(2);
+            skill_data
+        };
+        let skill_list = SkillList::new();
+        let character = TestCharacter::new(111);
+
+        let game_data = create_game_data(vec![create_skill_data()], 1);
+        assert!(matches!(
+            check_requirements(&game_data, skill_id(10), &skill_list, &character),
+            Err(LearnSkillError::PlanetRequirement)
+        ));
+
+        let game_data = create_game_data(vec![create_skill_data()], 2);
+        assert!(check_requirements(&game_data, skill_id(10), &skill_list, &character).is_ok());
+    }
+}
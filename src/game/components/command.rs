@@ -47,6 +47,10 @@ pub struct CommandCastSkill {
     pub skill_id: SkillId,
     pub skill_target: Option<CommandCastSkillTarget>,
     pub use_item: Option<(ItemSlot, Item)>,
+    pub npc_motion_id: Option<usize>,
+
+    // Identifies the SkillEvent sent when casting starts, assigned by command_system
+    pub cast_id: u32,
 }
 
 #[derive(Copy, Clone)]
@@ -174,6 +178,8 @@ impl NextCommand {
                 skill_id,
                 skill_target: None,
                 use_item,
+                npc_motion_id: None,
+                cast_id: 0,
             })),
             has_sent_server_message: false,
         }
@@ -189,6 +195,8 @@ impl NextCommand {
                 skill_id,
                 skill_target: Some(CommandCastSkillTarget::Entity(target_entity)),
                 use_item,
+                npc_motion_id: None,
+                cast_id: 0,
             })),
             has_sent_server_message: false,
         }
@@ -200,6 +208,25 @@ impl NextCommand {
                 skill_id,
                 skill_target: Some(CommandCastSkillTarget::Position(position)),
                 use_item: None,
+                npc_motion_id: None,
+                cast_id: 0,
+            })),
+            has_sent_server_message: false,
+        }
+    }
+
+    pub fn with_npc_cast_skill(
+        skill_id: SkillId,
+        skill_target: Option<CommandCastSkillTarget>,
+        npc_motion_id: usize,
+    ) -> Self {
+        Self {
+            command: Some(CommandData::CastSkill(CommandCastSkill {
+                skill_id,
+                skill_target,
+                use_item: None,
+                npc_motion_id: Some(npc_motion_id),
+                cast_id: 0,
             })),
             has_sent_server_message: false,
         }
@@ -303,9 +330,20 @@ impl Command {
         )
     }
 
+    pub fn with_hit_stun(duration: Duration) -> Self {
+        Self::new(
+            CommandData::Stop(CommandStop {
+                send_message: false,
+            }),
+            Some(duration),
+        )
+    }
+
     pub fn with_cast_skill(
         skill_id: SkillId,
         skill_target: Option<CommandCastSkillTarget>,
+        npc_motion_id: Option<usize>,
+        cast_id: u32,
         casting_duration: Duration,
         action_duration: Duration,
     ) -> Self {
@@ -314,6 +352,8 @@ impl Command {
                 skill_id,
                 skill_target,
                 use_item: None,
+                npc_motion_id,
+                cast_id,
             }),
             Some(casting_duration + action_duration),
         )
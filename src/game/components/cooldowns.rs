@@ -0,0 +1,88 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::{SkillCooldown, SkillData, SkillId};
+
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, Hash, PartialEq, Eq)]
+pub enum CooldownType {
+    Skill(SkillId),
+    SkillGroup(usize),
+    ConsumableItem(usize),
+}
+
+// Cooldowns are saved as the time remaining, so they persist across relog
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SavedCooldowns {
+    pub remaining: Vec<(CooldownType, Duration)>,
+}
+
+#[derive(Clone, Default)]
+pub struct Cooldowns {
+    pub expire_times: HashMap<CooldownType, Instant>,
+}
+
+impl Cooldowns {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn from_saved(saved: &SavedCooldowns, now: Instant) -> Self {
+        Self {
+            expire_times: saved
+                .remaining
+                .iter()
+                .map(|(cooldown_type, remaining)| (*cooldown_type, now + *remaining))
+                .collect(),
+        }
+    }
+
+    pub fn to_saved(&self, now: Instant) -> SavedCooldowns {
+        SavedCooldowns {
+            remaining: self
+                .expire_times
+                .iter()
+                .filter_map(|(cooldown_type, expire_time)| {
+                    expire_time
+                        .checked_duration_since(now)
+                        .map(|remaining| (*cooldown_type, remaining))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get_remaining(&self, cooldown_type: CooldownType, now: Instant) -> Option<Duration> {
+        self.expire_times
+            .get(&cooldown_type)
+            .and_then(|expire_time| expire_time.checked_duration_since(now))
+            .filter(|remaining| *remaining > Duration::from_secs(0))
+    }
+
+    pub fn set_cooldown(&mut self, cooldown_type: CooldownType, now: Instant, duration: Duration) {
+        if duration > Duration::from_secs(0) {
+            self.expire_times.insert(cooldown_type, now + duration);
+        }
+    }
+
+    pub fn get_skill_remaining(&self, skill_data: &SkillData, now: Instant) -> Option<Duration> {
+        let (cooldown_type, _) = get_skill_cooldown(skill_data);
+        self.get_remaining(cooldown_type, now)
+    }
+
+    pub fn set_skill_cooldown(&mut self, skill_data: &SkillData, now: Instant) {
+        let (cooldown_type, duration) = get_skill_cooldown(skill_data);
+        self.set_cooldown(cooldown_type, now, duration);
+    }
+}
+
+fn get_skill_cooldown(skill_data: &SkillData) -> (CooldownType, Duration) {
+    match skill_data.cooldown {
+        SkillCooldown::Skill(duration) => (CooldownType::Skill(skill_data.id), duration),
+        SkillCooldown::Group(ref group, duration) => {
+            (CooldownType::SkillGroup(group.0.get()), duration)
+        }
+    }
+}
@@ -0,0 +1,11 @@
+// Experience lost on the most recent death, which can be partially restored
+// by a resurrection skill before the character revives.
+pub struct DeathXpPenalty {
+    pub xp: u64,
+}
+
+impl DeathXpPenalty {
+    pub fn new(xp: u64) -> Self {
+        Self { xp }
+    }
+}
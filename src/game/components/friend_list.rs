@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+// The friend list packet sends the number of friends as a u8
+pub const MAX_FRIENDS: usize = u8::MAX as usize;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FriendListEntry {
+    pub tag: u32,
+    pub name: String,
+    pub blocked: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct FriendList {
+    pub friends: Vec<FriendListEntry>,
+
+    // Names of characters who have sent us a friend request which we have not yet answered
+    #[serde(skip)]
+    pub pending_requests: Vec<String>,
+
+    // Set once our friends have been notified that we are online
+    #[serde(skip)]
+    pub is_online: bool,
+}
+
+impl FriendList {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn find_by_tag(&self, tag: u32) -> Option<&FriendListEntry> {
+        self.friends.iter().find(|friend| friend.tag == tag)
+    }
+
+    pub fn find_by_tag_mut(&mut self, tag: u32) -> Option<&mut FriendListEntry> {
+        self.friends.iter_mut().find(|friend| friend.tag == tag)
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&FriendListEntry> {
+        self.friends.iter().find(|friend| friend.name == name)
+    }
+
+    pub fn is_blocked(&self, name: &str) -> bool {
+        self.find_by_name(name)
+            .map_or(false, |friend| friend.blocked)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.friends.len() >= MAX_FRIENDS
+    }
+
+    pub fn try_add_friend(&mut self, name: String) -> Option<&FriendListEntry> {
+        if self.is_full() || self.find_by_name(&name).is_some() {
+            return None;
+        }
+
+        let tag = self
+            .friends
+            .iter()
+            .map(|friend| friend.tag)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        self.friends.push(FriendListEntry {
+            tag,
+            name,
+            blocked: false,
+        });
+        self.friends.last()
+    }
+
+    pub fn remove_friend(&mut self, tag: u32) -> Option<FriendListEntry> {
+        let index = self.friends.iter().position(|friend| friend.tag == tag)?;
+        Some(self.friends.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_friend_until_full() {
+        let mut friend_list = FriendList::new();
+        for i in 0..MAX_FRIENDS {
+            assert!(friend_list.try_add_friend(format!("friend{}", i)).is_some());
+        }
+
+        assert!(friend_list.is_full());
+        assert!(friend_list
+            .try_add_friend(String::from("one_too_many"))
+            .is_none());
+        assert_eq!(friend_list.friends.len(), MAX_FRIENDS);
+    }
+
+    #[test]
+    fn add_existing_friend() {
+        let mut friend_list = FriendList::new();
+        assert!(friend_list.try_add_friend(String::from("friend")).is_some());
+        assert!(friend_list.try_add_friend(String::from("friend")).is_none());
+        assert_eq!(friend_list.friends.len(), 1);
+    }
+
+    #[test]
+    fn remove_friend_frees_space() {
+        let mut friend_list = FriendList::new();
+        for i in 0..MAX_FRIENDS {
+            friend_list.try_add_friend(format!("friend{}", i));
+        }
+
+        let tag = friend_list.find_by_name("friend0").unwrap().tag;
+        assert!(friend_list.remove_friend(tag).is_some());
+        assert!(!friend_list.is_full());
+        assert!(friend_list
+            .try_add_friend(String::from("new_friend"))
+            .is_some());
+    }
+}
@@ -0,0 +1,13 @@
+use std::time::{Duration, Instant};
+
+pub const SHOUT_COOLDOWN_DURATION: Duration = Duration::from_secs(10);
+
+pub struct LastShoutTime {
+    pub when: Instant,
+}
+
+impl LastShoutTime {
+    pub fn new(when: Instant) -> Self {
+        Self { when }
+    }
+}
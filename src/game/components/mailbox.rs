@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{data::item::Item, game::components::Money};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Mail {
+    pub from: String,
+    pub text: String,
+    pub item: Option<Item>,
+    pub money: Option<Money>,
+    pub is_read: bool,
+}
+
+impl Mail {
+    pub fn new(from: String, text: String, item: Option<Item>, money: Option<Money>) -> Self {
+        Self {
+            from,
+            text,
+            item,
+            money,
+            is_read: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Mailbox {
+    pub mail: Vec<Mail>,
+}
+
+impl Mailbox {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
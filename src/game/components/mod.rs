@@ -8,20 +8,25 @@ mod character_list;
 mod client_entity;
 mod client_entity_visibility;
 mod command;
+mod cooldowns;
 mod damage_sources;
+mod death_xp_penalty;
 mod destination;
 mod dropped_item;
 mod equipment;
 mod event_object;
 mod experience_points;
+mod friend_list;
 mod spawn_expire_time;
 mod owned_expire_time;
 mod game_client;
 mod health_points;
 mod hotbar;
 mod inventory;
+mod last_shout_time;
 mod level;
 mod login_client;
+mod mailbox;
 mod mana_points;
 mod monster_spawn_point;
 mod motion_data;
@@ -62,20 +67,25 @@ pub use command::{
     Command, CommandAttack, CommandCastSkill, CommandCastSkillTarget, CommandData, CommandDie,
     CommandMove, CommandPickupDroppedItem, CommandSit, CommandStop, NextCommand,
 };
+pub use cooldowns::{CooldownType, Cooldowns, SavedCooldowns};
 pub use damage_sources::{DamageSource, DamageSources};
+pub use death_xp_penalty::DeathXpPenalty;
 pub use destination::Destination;
 pub use dropped_item::DroppedItem;
 pub use equipment::*;
 pub use event_object::EventObject;
 pub use experience_points::ExperiencePoints;
+pub use friend_list::{FriendList, FriendListEntry};
 pub use spawn_expire_time::SpawnExpireTime;
 pub use owned_expire_time::OwnedExpireTime;
 pub use game_client::*;
 pub use health_points::HealthPoints;
 pub use hotbar::{Hotbar, HotbarSlot};
 pub use inventory::*;
+pub use last_shout_time::{LastShoutTime, SHOUT_COOLDOWN_DURATION};
 pub use level::Level;
 pub use login_client::*;
+pub use mailbox::{Mail, Mailbox};
 pub use mana_points::ManaPoints;
 pub use monster_spawn_point::MonsterSpawnPoint;
 pub use motion_data::{MotionData, MotionDataCharacter, MotionDataNpc};
@@ -96,7 +106,7 @@ pub use skill_points::SkillPoints;
 pub use spawn_origin::SpawnOrigin;
 pub use stamina::{Stamina, MAX_STAMINA};
 pub use stat_points::StatPoints;
-pub use status_effects::StatusEffects;
+pub use status_effects::{ActiveStatusEffect, StatusEffects, STATUS_EFFECT_TICK_DURATION};
 pub use target::Target;
 pub use team::Team;
 pub use union_membership::UnionMembership;
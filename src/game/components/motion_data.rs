@@ -67,6 +67,13 @@ impl MotionData {
         }
     }
 
+    pub fn get_hit(&self) -> Option<&MotionFileData> {
+        match self {
+            MotionData::Character(character) => character.hit.as_ref(),
+            MotionData::Npc(npc) => npc.hit.as_ref(),
+        }
+    }
+
     pub fn get_pickup_dropped_item(&self) -> Option<&MotionFileData> {
         match self {
             MotionData::Character(character) => character.pickup_dropped_item.as_ref(),
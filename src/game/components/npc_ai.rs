@@ -10,6 +10,7 @@ pub struct NpcAi {
     pub has_run_created_trigger: bool,
     pub pending_damage: Vec<(Entity, Damage)>,
     pub has_run_dead_ai: bool,
+    pub is_trace_enabled: bool,
 }
 
 impl NpcAi {
@@ -20,6 +21,7 @@ impl NpcAi {
             has_run_created_trigger: false,
             pending_damage: Vec::new(),
             has_run_dead_ai: false,
+            is_trace_enabled: false,
         }
     }
 }
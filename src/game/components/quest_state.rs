@@ -112,6 +112,40 @@ impl QuestState {
         None
     }
 
+    // Quest items given outside of a quest trigger have no selected quest, so add to
+    // a quest which already has the item, else the first quest with space for it.
+    pub fn try_add_quest_item(&mut self, item: Item) -> Result<usize, Item> {
+        let item_reference = item.get_item_reference();
+        let quest_index = self.active_quests.iter().position(|active_quest| {
+            active_quest.as_ref().map_or(false, |active_quest| {
+                active_quest.find_item(item_reference).is_some()
+            })
+        });
+
+        let mut item = item;
+        if let Some(quest_index) = quest_index {
+            match self.active_quests[quest_index]
+                .as_mut()
+                .unwrap()
+                .try_add_item(item)
+            {
+                Ok(_) => return Ok(quest_index),
+                Err(not_added_item) => item = not_added_item,
+            }
+        }
+
+        for (quest_index, active_quest) in self.active_quests.iter_mut().enumerate() {
+            if let Some(active_quest) = active_quest {
+                match active_quest.try_add_item(item) {
+                    Ok(_) => return Ok(quest_index),
+                    Err(not_added_item) => item = not_added_item,
+                }
+            }
+        }
+
+        Err(item)
+    }
+
     pub fn get_quest(&self, index: usize) -> Option<&ActiveQuest> {
         self.active_quests.get(index).and_then(|x| x.as_ref())
     }
@@ -124,3 +158,80 @@ impl QuestState {
         self.active_quests.get_mut(index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::item::ItemType;
+
+    fn quest_item(item_number: usize, quantity: u32) -> Item {
+        Item::new(&ItemReference::new(ItemType::Quest, item_number), quantity).unwrap()
+    }
+
+    #[test]
+    fn add_quest_item_without_active_quest() {
+        let mut quest_state = QuestState::new();
+        assert!(quest_state.try_add_quest_item(quest_item(1, 1)).is_err());
+    }
+
+    #[test]
+    fn add_quest_item_to_first_active_quest() {
+        let mut quest_state = QuestState::new();
+        quest_state.active_quests[2] = Some(ActiveQuest::new(10, None));
+        quest_state.active_quests[5] = Some(ActiveQuest::new(20, None));
+
+        assert_eq!(
+            quest_state.try_add_quest_item(quest_item(1, 1)).ok(),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn add_quest_item_to_quest_with_same_item() {
+        let mut quest_state = QuestState::new();
+        quest_state.active_quests[0] = Some(ActiveQuest::new(10, None));
+        quest_state.active_quests[1] = Some(ActiveQuest::new(20, None));
+        quest_state.active_quests[1]
+            .as_mut()
+            .unwrap()
+            .try_add_item(quest_item(1, 1))
+            .unwrap();
+
+        assert_eq!(
+            quest_state.try_add_quest_item(quest_item(1, 2)).ok(),
+            Some(1)
+        );
+        assert_eq!(
+            quest_state
+                .get_quest(1)
+                .and_then(|active_quest| {
+                    active_quest.find_item(ItemReference::new(ItemType::Quest, 1))
+                })
+                .map(|item| item.get_quantity()),
+            Some(3)
+        );
+        assert!(quest_state
+            .get_quest(0)
+            .and_then(|active_quest| active_quest.find_item(ItemReference::new(ItemType::Quest, 1)))
+            .is_none());
+    }
+
+    #[test]
+    fn add_quest_item_skips_full_quest() {
+        let mut quest_state = QuestState::new();
+        quest_state.active_quests[0] = Some(ActiveQuest::new(10, None));
+        quest_state.active_quests[1] = Some(ActiveQuest::new(20, None));
+        for item_number in 1..=6 {
+            quest_state.active_quests[0]
+                .as_mut()
+                .unwrap()
+                .try_add_item(quest_item(item_number, 1))
+                .unwrap();
+        }
+
+        assert_eq!(
+            quest_state.try_add_quest_item(quest_item(7, 1)).ok(),
+            Some(1)
+        );
+    }
+}
@@ -1,14 +1,29 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use enum_map::EnumMap;
 use log::warn;
 
 use crate::data::{StatusEffectData, StatusEffectType};
 
+pub const STATUS_EFFECT_TICK_DURATION: Duration = Duration::from_secs(1);
+
 #[derive(Clone)]
 pub struct ActiveStatusEffect {
     pub value: i32,
     pub expire_time: Instant,
+
+    // When the value of a per tick status effect is next applied
+    pub next_tick_time: Option<Instant>,
+}
+
+impl ActiveStatusEffect {
+    pub fn new(value: i32, expire_time: Instant) -> Self {
+        Self {
+            value,
+            expire_time,
+            next_tick_time: None,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -43,8 +58,7 @@ impl StatusEffects {
             StatusEffectType::ClearGood
             | StatusEffectType::ClearBad
             | StatusEffectType::ClearAll
-            | StatusEffectType::ClearInvisible
-            | StatusEffectType::DecreaseLifeTime => {
+            | StatusEffectType::ClearInvisible => {
                 warn!(
                     "Unimplemented apply_status_effect for type {:?}",
                     status_effect_type
@@ -52,12 +66,29 @@ impl StatusEffects {
                 false
             }
             _ => {
-                self.active[status_effect_type] = Some(ActiveStatusEffect { value, expire_time });
+                self.active[status_effect_type] = Some(ActiveStatusEffect::new(value, expire_time));
                 true
             }
         }
     }
 
+    // Stunned or sleeping entities can not move, attack or cast skills
+    pub fn is_disabled(&self) -> bool {
+        self.active[StatusEffectType::Fainting].is_some()
+            || self.active[StatusEffectType::Sleep].is_some()
+    }
+
+    // Silenced entities can not cast skills
+    pub fn is_silenced(&self) -> bool {
+        self.active[StatusEffectType::Dumb].is_some()
+    }
+
+    // Invisible entities can only be seen by their allies
+    pub fn is_invisible(&self) -> bool {
+        self.active[StatusEffectType::Transparent].is_some()
+            || self.active[StatusEffectType::Disguise].is_some()
+    }
+
     pub fn get_status_effect_value(&self, status_effect_type: StatusEffectType) -> Option<i32> {
         self.active[status_effect_type]
             .as_ref()
@@ -21,10 +21,24 @@ pub struct DamageEventTagged {
     pub defender: Entity,
 }
 
+// Kills the defender outright, when attacker is the defender there is no killer
+pub struct DamageEventKill {
+    pub attacker: Entity,
+    pub defender: Entity,
+}
+
+// Damage over time from a status effect such as poison, there is no attacker
+pub struct DamageEventStatusEffect {
+    pub defender: Entity,
+    pub damage: Damage,
+}
+
 pub enum DamageEvent {
     Attack(DamageEventAttack),
     Skill(DamageEventSkill),
     Tagged(DamageEventTagged),
+    Kill(DamageEventKill),
+    StatusEffect(DamageEventStatusEffect),
 }
 
 impl DamageEvent {
@@ -55,4 +69,12 @@ impl DamageEvent {
     pub fn with_tagged(attacker: Entity, defender: Entity) -> Self {
         Self::Tagged(DamageEventTagged { attacker, defender })
     }
+
+    pub fn with_kill(attacker: Entity, defender: Entity) -> Self {
+        Self::Kill(DamageEventKill { attacker, defender })
+    }
+
+    pub fn with_status_effect(defender: Entity, damage: Damage) -> Self {
+        Self::StatusEffect(DamageEventStatusEffect { defender, damage })
+    }
 }
@@ -0,0 +1,17 @@
+use bevy_ecs::prelude::Entity;
+
+use crate::game::components::{ItemSlot, Money};
+
+pub struct MailEventSend {
+    pub sender_entity: Entity,
+    pub recipient_name: String,
+    pub text: String,
+    pub item_slot: Option<ItemSlot>,
+    pub item_quantity: Option<u32>,
+    pub money: Option<Money>,
+}
+
+pub enum MailEvent {
+    Send(MailEventSend),
+    Deliver(Entity),
+}
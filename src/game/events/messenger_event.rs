@@ -0,0 +1,12 @@
+use bevy_ecs::prelude::Entity;
+
+pub enum MessengerEvent {
+    Login(Entity),
+    Logout(Entity),
+    AddFriendRequest(Entity, String),
+    AddFriendAccept(Entity, String),
+    AddFriendReject(Entity, String),
+    DeleteFriend(Entity, u32),
+    ToggleBlockFriend(Entity, u32),
+    Chat(Entity, u32, String),
+}
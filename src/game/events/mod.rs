@@ -1,15 +1,22 @@
 mod chat_command_event;
 mod damage_event;
+mod mail_event;
+mod messenger_event;
 mod npc_store_event;
 mod personal_store_event;
 mod quest_trigger_event;
 mod reward_xp_event;
 mod save_event;
+mod skill_cast_interrupted_event;
 mod skill_event;
 mod use_item_event;
 
 pub use chat_command_event::ChatCommandEvent;
-pub use damage_event::{DamageEvent, DamageEventAttack, DamageEventSkill, DamageEventTagged};
+pub use damage_event::{
+    DamageEvent, DamageEventAttack, DamageEventKill, DamageEventSkill, DamageEventTagged,
+};
+pub use mail_event::{MailEvent, MailEventSend};
+pub use messenger_event::MessengerEvent;
 pub use npc_store_event::NpcStoreEvent;
 pub use personal_store_event::{
     PersonalStoreEvent, PersonalStoreEventBuyItem, PersonalStoreEventListItems,
@@ -17,5 +24,6 @@ pub use personal_store_event::{
 pub use quest_trigger_event::QuestTriggerEvent;
 pub use reward_xp_event::RewardXpEvent;
 pub use save_event::{SaveEvent, SaveEventCharacter};
+pub use skill_cast_interrupted_event::SkillCastInterruptedEvent;
 pub use skill_event::{SkillEvent, SkillEventTarget};
 pub use use_item_event::UseItemEvent;
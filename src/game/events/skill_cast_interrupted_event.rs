@@ -0,0 +1,17 @@
+use bevy_ecs::prelude::Entity;
+
+// The cast was interrupted before its skill effect was applied, so the pending
+// SkillEvent with the same cast id must be discarded.
+pub struct SkillCastInterruptedEvent {
+    pub caster_entity: Entity,
+    pub cast_id: u32,
+}
+
+impl SkillCastInterruptedEvent {
+    pub fn new(caster_entity: Entity, cast_id: u32) -> Self {
+        Self {
+            caster_entity,
+            cast_id,
+        }
+    }
+}
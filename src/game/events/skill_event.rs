@@ -17,6 +17,7 @@ pub enum SkillEventTarget {
 #[derive(Clone)]
 pub struct SkillEvent {
     pub caster_entity: Entity,
+    pub cast_id: u32,
     pub when: Instant,
     pub skill_id: SkillId,
     pub skill_target: SkillEventTarget,
@@ -26,6 +27,7 @@ pub struct SkillEvent {
 impl SkillEvent {
     pub fn new(
         caster_entity: Entity,
+        cast_id: u32,
         when: Instant,
         skill_id: SkillId,
         skill_target: SkillEventTarget,
@@ -33,6 +35,7 @@ impl SkillEvent {
     ) -> Self {
         Self {
             caster_entity,
+            cast_id,
             when,
             skill_id,
             skill_target,
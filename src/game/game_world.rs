@@ -6,28 +6,33 @@ use bevy_ecs::{
 use chrono::Local;
 use crossbeam_channel::Receiver;
 use log::debug;
-use std::time::{Duration, Instant};
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
 
 use crate::game::{
     events::{
-        ChatCommandEvent, DamageEvent, NpcStoreEvent, PersonalStoreEvent, QuestTriggerEvent,
-        RewardXpEvent, SaveEvent, SkillEvent, UseItemEvent,
+        ChatCommandEvent, DamageEvent, MailEvent, MessengerEvent, NpcStoreEvent,
+        PersonalStoreEvent, QuestTriggerEvent, RewardXpEvent, SaveEvent, SkillCastInterruptedEvent,
+        SkillEvent, UseItemEvent,
     },
     messages::control::ControlMessage,
     resources::{
-        BotList, ClientEntityList, ControlChannel, GameData, LoginTokens, ServerList,
-        ServerMessages, ServerTime, WorldRates, WorldTime, ZoneList,
+        BotList, ClientEntityList, ControlChannel, GameConfig, GameData, LoginTokens,
+        PendingQuestTriggerList, ScriptEngine, ServerList, ServerMessages, ServerTime, WorldRates,
+        WorldTime, WorldVariables, ZoneList,
     },
     systems::{
         ability_values_system, bot_ai_system, chat_commands_system,
         client_entity_visibility_system, command_system, control_server_system, damage_system,
         experience_points_system, expire_time_system, game_server_authentication_system,
         game_server_join_system, game_server_main_system, login_server_authentication_system,
-        login_server_system, monster_spawn_system, npc_ai_system, npc_store_system,
-        passive_recovery_system, personal_store_system, quest_system, save_system,
-        server_messages_system, skill_effect_system, startup_zones_system, status_effect_system,
-        update_position_system, use_item_system, weight_system, world_server_authentication_system,
-        world_server_system, world_time_system,
+        login_server_system, mail_system, messenger_system, monster_spawn_system, npc_ai_system,
+        npc_store_system, passive_recovery_system, personal_store_system, quest_system,
+        save_system, server_messages_system, skill_effect_system, startup_zones_system,
+        status_effect_system, update_position_system, use_item_system, weight_system,
+        world_server_authentication_system, world_server_system, world_time_system,
     },
 };
 
@@ -55,26 +60,33 @@ impl GameWorld {
         }
     }
 
-    pub fn run(&mut self, game_data: GameData) {
+    pub fn run(&mut self, game_data: GameData, game_config: GameConfig) {
         let mut world = World::new();
         world.insert_resource(BotList::new());
         world.insert_resource(ClientEntityList::new(&game_data.zones));
         world.insert_resource(ControlChannel::new(self.control_rx.clone()));
         world.insert_resource(game_data);
+        world.insert_resource(game_config);
         world.insert_resource(LoginTokens::new());
+        world.insert_resource(PendingQuestTriggerList::new());
+        world.insert_resource(ScriptEngine::load(Path::new("scripts")));
         world.insert_resource(ServerList::new());
         world.insert_resource(ServerMessages::new());
         world.insert_resource(WorldRates::new());
         world.insert_resource(WorldTime::new());
+        world.insert_resource(WorldVariables::new());
         world.insert_resource(ZoneList::new());
 
         world.insert_resource(Events::<ChatCommandEvent>::default());
         world.insert_resource(Events::<DamageEvent>::default());
+        world.insert_resource(Events::<MailEvent>::default());
+        world.insert_resource(Events::<MessengerEvent>::default());
         world.insert_resource(Events::<NpcStoreEvent>::default());
         world.insert_resource(Events::<PersonalStoreEvent>::default());
         world.insert_resource(Events::<QuestTriggerEvent>::default());
         world.insert_resource(Events::<RewardXpEvent>::default());
         world.insert_resource(Events::<SaveEvent>::default());
+        world.insert_resource(Events::<SkillCastInterruptedEvent>::default());
         world.insert_resource(Events::<SkillEvent>::default());
         world.insert_resource(Events::<UseItemEvent>::default());
 
@@ -91,10 +103,13 @@ impl GameWorld {
             SystemStage::parallel()
                 .with_system(Events::<ChatCommandEvent>::update_system)
                 .with_system(Events::<DamageEvent>::update_system)
+                .with_system(Events::<MailEvent>::update_system)
+                .with_system(Events::<MessengerEvent>::update_system)
                 .with_system(Events::<PersonalStoreEvent>::update_system)
                 .with_system(Events::<QuestTriggerEvent>::update_system)
                 .with_system(Events::<RewardXpEvent>::update_system)
                 .with_system(Events::<SaveEvent>::update_system)
+                .with_system(Events::<SkillCastInterruptedEvent>::update_system)
                 .with_system(Events::<SkillEvent>::update_system)
                 .with_system(Events::<UseItemEvent>::update_system),
         );
@@ -136,6 +151,8 @@ impl GameWorld {
                 .with_system(personal_store_system.system())
                 .with_system(npc_store_system.system())
                 .with_system(damage_system.system())
+                .with_system(mail_system.system())
+                .with_system(messenger_system.system())
                 .with_system(quest_system.system())
                 .with_system(use_item_system.system()),
         );
@@ -185,6 +185,7 @@ pub struct JoinZoneResponse {
     pub health_points: HealthPoints,
     pub mana_points: ManaPoints,
     pub world_ticks: WorldTicks,
+    pub is_pvp_enabled: bool,
 }
 
 #[derive(Debug)]
@@ -281,6 +282,8 @@ pub enum ClientMessage {
     GameConnectionRequest(GameConnectionRequest),
     JoinZoneRequest(JoinZoneRequest),
     Chat(String),
+    Whisper(String, String),
+    ShoutChat(String),
     Move(Move),
     Attack(Attack),
     SetHotbarSlot(SetHotbarSlot),
@@ -305,4 +308,11 @@ pub enum ClientMessage {
     SitToggle,
     DriveToggle,
     UseEmote(MotionId, bool),
+    MessengerAddFriend(String),
+    MessengerAddFriendAccept(String),
+    MessengerAddFriendReject(String),
+    MessengerDeleteFriend(u32),
+    MessengerToggleBlockFriend(u32),
+    MessengerChat(u32, String),
+    SendMail(String, String),
 }
@@ -250,13 +250,14 @@ pub struct QuestDeleteResult {
     pub quest_id: usize,
 }
 
-#[allow(dead_code)]
 #[derive(Copy, Clone, Debug)]
 pub enum LearnSkillError {
     AlreadyLearnt,
     JobRequirement,
     SkillRequirement,
     AbilityRequirement,
+    UnionRequirement,
+    PlanetRequirement,
     Full,
     InvalidSkillId,
     SkillPointRequirement,
@@ -361,6 +362,7 @@ pub enum CancelCastingSkillReason {
     NeedAbility,
     NeedTarget,
     InvalidTarget,
+    Interrupted,
 }
 
 #[allow(dead_code)]
@@ -373,6 +375,9 @@ pub enum NpcStoreTransactionError {
     NotEnoughMoney,
     NotSameUnion,
     NotEnoughUnionPoints,
+    InvalidItem,
+    InventoryFull,
+    MoneyOverflow,
 }
 
 #[derive(Clone)]
@@ -389,6 +394,27 @@ pub struct UseEmote {
     pub is_stop: bool,
 }
 
+#[derive(Clone, Copy)]
+pub enum MessengerFriendStatus {
+    Online,
+    Offline,
+    Blocked,
+    Deleted,
+}
+
+#[derive(Clone)]
+pub struct MessengerFriend {
+    pub tag: u32,
+    pub name: String,
+    pub status: MessengerFriendStatus,
+}
+
+#[derive(Clone)]
+pub struct MessengerChat {
+    pub tag: u32,
+    pub text: String,
+}
+
 #[derive(Clone)]
 pub enum ServerMessage {
     AttackEntity(AttackEntity),
@@ -424,6 +450,7 @@ pub enum ServerMessage {
     QuestDeleteResult(QuestDeleteResult),
     LearnSkillResult(Result<LearnSkillSuccess, LearnSkillError>),
     RunNpcDeathTrigger(NpcId),
+    ChangeNpcId(ClientEntityId, NpcId),
     OpenPersonalStore(OpenPersonalStore),
     PersonalStoreItemList(PersonalStoreItemList),
     PersonalStoreTransactionResult(PersonalStoreTransactionResult),
@@ -439,4 +466,12 @@ pub enum ServerMessage {
     MoveToggle(MoveToggle),
     SitToggle(ClientEntityId),
     UseEmote(UseEmote),
+    MessengerFriendList(Vec<MessengerFriend>),
+    MessengerAddFriendRequest(ClientEntityId, String),
+    MessengerAddFriendAccepted(MessengerFriend),
+    MessengerAddFriendRejected(String),
+    MessengerAddFriendNotFound(String),
+    MessengerFriendStatus(u32, MessengerFriendStatus),
+    MessengerChat(MessengerChat),
+    UpdateZonePvpEnabled(bool),
 }
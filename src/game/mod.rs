@@ -4,7 +4,11 @@ mod game_world;
 mod resources;
 mod systems;
 
+#[cfg(test)]
+pub mod test_utils;
+
 pub mod components;
 pub mod messages;
 pub use game_world::GameWorld;
-pub use resources::GameData;
+pub use resources::{GameConfig, GameData, HitStunDamageSource};
+pub use systems::{is_quest_condition_implemented, is_quest_reward_implemented};
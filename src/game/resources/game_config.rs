@@ -0,0 +1,47 @@
+use std::collections::HashSet;
+
+use crate::data::NpcId;
+
+pub const DEFAULT_MAX_CHARACTER_LEVEL: u32 = 210;
+pub const DEFAULT_DEATH_XP_PENALTY_PERCENT: u32 = 3;
+pub const DEFAULT_REVIVE_POSITION_XP_PENALTY_PERCENT: u32 = 2;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HitStunDamageSource {
+    Attack,
+    Skill,
+}
+
+pub struct GameConfig {
+    pub max_character_level: u32,
+
+    // Percentage of the current level's required xp lost when killed by a monster
+    // outside of a PvP zone.
+    pub death_xp_penalty_percent: u32,
+
+    // Additional percentage lost when reviving at the nearest revive point rather
+    // than returning to the save point.
+    pub revive_position_xp_penalty_percent: u32,
+
+    // Damage sources which are able to interrupt movement and casting with hit stun,
+    // other damage such as status effects never applies hit stun.
+    pub hit_stun_damage_sources: HashSet<HitStunDamageSource>,
+
+    // Monsters which are never interrupted by hit stun, such as bosses.
+    pub hit_stun_immune_npc_ids: HashSet<NpcId>,
+}
+
+impl GameConfig {
+    pub fn new() -> Self {
+        Self {
+            max_character_level: DEFAULT_MAX_CHARACTER_LEVEL,
+            death_xp_penalty_percent: DEFAULT_DEATH_XP_PENALTY_PERCENT,
+            revive_position_xp_penalty_percent: DEFAULT_REVIVE_POSITION_XP_PENALTY_PERCENT,
+            hit_stun_damage_sources: [HitStunDamageSource::Attack, HitStunDamageSource::Skill]
+                .iter()
+                .copied()
+                .collect(),
+            hit_stun_immune_npc_ids: HashSet::new(),
+        }
+    }
+}
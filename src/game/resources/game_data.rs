@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
 use crate::data::{
-    AbilityValueCalculator, AiDatabase, CharacterCreator, DropTable, ItemDatabase, MotionDatabase,
-    NpcDatabase, QuestDatabase, SkillDatabase, StatusEffectDatabase, ZoneDatabase,
+    AbilityValueCalculator, AiDatabase, CharacterCreator, DropTable, ItemDatabase,
+    JobClassDatabase, MotionDatabase, NpcDatabase, QuestDatabase, SkillDatabase,
+    StatusEffectDatabase, ZoneDatabase,
 };
 
 pub struct GameData {
@@ -11,6 +12,7 @@ pub struct GameData {
     pub drop_table: Box<dyn DropTable + Send + Sync>,
     pub ai: Arc<AiDatabase>,
     pub items: Arc<ItemDatabase>,
+    pub job_classes: Arc<JobClassDatabase>,
     pub motions: Arc<MotionDatabase>,
     pub npcs: Arc<NpcDatabase>,
     pub quests: Arc<QuestDatabase>,
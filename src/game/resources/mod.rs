@@ -1,23 +1,33 @@
 mod bot_list;
 mod client_entity_list;
 mod control_channel;
+mod game_config;
 mod game_data;
 mod login_tokens;
+mod pending_quest_trigger_list;
+mod script_engine;
 mod server_list;
 mod server_messages;
 mod server_time;
 mod world_rates;
 mod world_time;
+mod world_variables;
 mod zone_list;
 
 pub use bot_list::{BotList, BotListEntry};
 pub use client_entity_list::{ClientEntityList, ClientEntitySet, ClientEntityZone};
 pub use control_channel::ControlChannel;
+pub use game_config::{GameConfig, HitStunDamageSource};
 pub use game_data::GameData;
 pub use login_tokens::{LoginToken, LoginTokens};
+pub use pending_quest_trigger_list::{
+    PendingQuestTrigger, PendingQuestTriggerList, PendingQuestTriggerTarget,
+};
+pub use script_engine::{ScriptContext, ScriptEffect, ScriptEngine};
 pub use server_list::{GameServer, ServerList, WorldServer};
 pub use server_messages::ServerMessages;
 pub use server_time::ServerTime;
 pub use world_rates::WorldRates;
 pub use world_time::WorldTime;
+pub use world_variables::WorldVariables;
 pub use zone_list::ZoneList;
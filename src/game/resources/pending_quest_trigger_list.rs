@@ -0,0 +1,60 @@
+use bevy_ecs::prelude::Entity;
+use std::time::Instant;
+
+use crate::data::{QuestTriggerHash, ZoneId};
+
+pub enum PendingQuestTriggerTarget {
+    Entity {
+        trigger_entity: Entity,
+        selected_npc: Option<Entity>,
+        selected_event_object: Option<Entity>,
+    },
+    ZoneTeam {
+        zone_id: ZoneId,
+        team_number: u32,
+    },
+}
+
+pub struct PendingQuestTrigger {
+    pub when: Instant,
+    pub trigger_hash: QuestTriggerHash,
+    pub target: PendingQuestTriggerTarget,
+}
+
+pub struct PendingQuestTriggerList {
+    pub pending: Vec<PendingQuestTrigger>,
+}
+
+impl PendingQuestTriggerList {
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn push(
+        &mut self,
+        when: Instant,
+        trigger_hash: QuestTriggerHash,
+        target: PendingQuestTriggerTarget,
+    ) {
+        self.pending.push(PendingQuestTrigger {
+            when,
+            trigger_hash,
+            target,
+        });
+    }
+
+    pub fn take_expired(&mut self, now: Instant) -> Vec<PendingQuestTrigger> {
+        let mut expired = Vec::new();
+        let mut i = 0;
+        while i < self.pending.len() {
+            if self.pending[i].when <= now {
+                expired.push(self.pending.remove(i));
+            } else {
+                i += 1;
+            }
+        }
+        expired
+    }
+}
@@ -0,0 +1,205 @@
+use log::{debug, info, warn};
+use num_traits::FromPrimitive;
+use rhai::{Dynamic, Engine, Scope, AST};
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use crate::data::{item::ItemType, ItemReference};
+
+const SCRIPT_MAX_OPERATIONS: u64 = 100_000;
+const SCRIPT_MAX_CALL_LEVELS: usize = 32;
+const SCRIPT_MAX_STRING_SIZE: usize = 1024;
+const SCRIPT_MAX_ARRAY_SIZE: usize = 1024;
+
+pub enum ScriptEffect {
+    AddExperience(u64),
+    AddItem(ItemReference, u32),
+    AddMoney(i64),
+    Announce(String),
+    SetQuestSwitch(usize, bool),
+    SetWorldVariable(usize, i32),
+    Whisper(String),
+}
+
+// The only view a script has of the game world, scripts read from a snapshot
+// and queue effects which are validated and applied by the calling system.
+#[derive(Clone)]
+pub struct ScriptContext {
+    name: String,
+    level: i64,
+    money: i64,
+    zone_id: i64,
+    world_variables: Vec<i32>,
+    effects: Arc<Mutex<Vec<ScriptEffect>>>,
+}
+
+impl ScriptContext {
+    pub fn new(
+        name: String,
+        level: i64,
+        money: i64,
+        zone_id: i64,
+        world_variables: Vec<i32>,
+    ) -> Self {
+        Self {
+            name,
+            level,
+            money,
+            zone_id,
+            world_variables,
+            effects: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn take_effects(&self) -> Vec<ScriptEffect> {
+        std::mem::take(&mut *self.effects.lock().unwrap())
+    }
+
+    fn push_effect(&mut self, effect: ScriptEffect) {
+        self.effects.lock().unwrap().push(effect);
+    }
+
+    fn get_name(&mut self) -> String {
+        self.name.clone()
+    }
+
+    fn get_level(&mut self) -> i64 {
+        self.level
+    }
+
+    fn get_money(&mut self) -> i64 {
+        self.money
+    }
+
+    fn get_zone(&mut self) -> i64 {
+        self.zone_id
+    }
+
+    fn world_variable(&mut self, index: i64) -> i64 {
+        self.world_variables
+            .get(index as usize)
+            .copied()
+            .unwrap_or(0) as i64
+    }
+
+    fn add_experience(&mut self, xp: i64) {
+        if xp > 0 {
+            self.push_effect(ScriptEffect::AddExperience(xp as u64));
+        }
+    }
+
+    fn add_item(&mut self, item_type: i64, item_number: i64, quantity: i64) {
+        match FromPrimitive::from_i64(item_type) {
+            Some(item_type) if item_number > 0 && quantity > 0 => {
+                self.push_effect(ScriptEffect::AddItem(
+                    ItemReference::new(item_type, item_number as usize),
+                    quantity as u32,
+                ));
+            }
+            _ => warn!(
+                "Script add_item called with invalid item {}:{} x{}",
+                item_type, item_number, quantity
+            ),
+        }
+    }
+
+    fn add_money(&mut self, amount: i64) {
+        self.push_effect(ScriptEffect::AddMoney(amount));
+    }
+
+    fn announce(&mut self, text: &str) {
+        self.push_effect(ScriptEffect::Announce(text.to_string()));
+    }
+
+    fn set_quest_switch(&mut self, switch_id: i64, value: bool) {
+        if switch_id >= 0 {
+            self.push_effect(ScriptEffect::SetQuestSwitch(switch_id as usize, value));
+        }
+    }
+
+    fn set_world_variable(&mut self, index: i64, value: i64) {
+        if index >= 0 {
+            self.push_effect(ScriptEffect::SetWorldVariable(index as usize, value as i32));
+        }
+    }
+
+    fn whisper(&mut self, text: &str) {
+        self.push_effect(ScriptEffect::Whisper(text.to_string()));
+    }
+}
+
+pub struct ScriptEngine {
+    engine: Engine,
+    ast: AST,
+}
+
+impl ScriptEngine {
+    pub fn load(path: &Path) -> Self {
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(SCRIPT_MAX_OPERATIONS)
+            .set_max_call_levels(SCRIPT_MAX_CALL_LEVELS)
+            .set_max_string_size(SCRIPT_MAX_STRING_SIZE)
+            .set_max_array_size(SCRIPT_MAX_ARRAY_SIZE)
+            .on_print(|text| info!("Script: {}", text));
+
+        engine
+            .register_type_with_name::<ScriptContext>("Context")
+            .register_get("name", ScriptContext::get_name)
+            .register_get("level", ScriptContext::get_level)
+            .register_get("money", ScriptContext::get_money)
+            .register_get("zone", ScriptContext::get_zone)
+            .register_fn("world_variable", ScriptContext::world_variable)
+            .register_fn("add_experience", ScriptContext::add_experience)
+            .register_fn("add_item", ScriptContext::add_item)
+            .register_fn("add_money", ScriptContext::add_money)
+            .register_fn("announce", ScriptContext::announce)
+            .register_fn("set_quest_switch", ScriptContext::set_quest_switch)
+            .register_fn("set_world_variable", ScriptContext::set_world_variable)
+            .register_fn("whisper", ScriptContext::whisper);
+
+        let mut ast = AST::empty();
+        if let Ok(entries) = std::fs::read_dir(path) {
+            for entry in entries.flatten() {
+                let script_path = entry.path();
+                if script_path.extension().map_or(true, |ext| ext != "rhai") {
+                    continue;
+                }
+
+                match engine.compile_file(script_path.clone()) {
+                    Ok(script_ast) => ast = ast.merge(&script_ast),
+                    Err(error) => warn!(
+                        "Failed to compile script {}, error: {}",
+                        script_path.display(),
+                        error
+                    ),
+                }
+            }
+        }
+
+        debug!("Loaded {} script functions", ast.iter_functions().count());
+        Self { engine, ast }
+    }
+
+    pub fn has_function(&self, function_name: &str) -> bool {
+        self.ast
+            .iter_functions()
+            .any(|function| function.name == function_name)
+    }
+
+    // Returns the boolean result of the script function, scripts which do
+    // not return a bool are treated as successful.
+    pub fn call(&self, function_name: &str, context: &ScriptContext) -> Result<bool, String> {
+        self.engine
+            .call_fn::<Dynamic>(
+                &mut Scope::new(),
+                &self.ast,
+                function_name,
+                (context.clone(),),
+            )
+            .map(|result| result.as_bool().unwrap_or(true))
+            .map_err(|error| error.to_string())
+    }
+}
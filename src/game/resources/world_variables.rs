@@ -0,0 +1,16 @@
+pub const WORLD_VARIABLES_COUNT: usize = 50;
+pub const ECONOMY_VARIABLES_COUNT: usize = 50;
+
+pub struct WorldVariables {
+    pub world: Vec<i32>,
+    pub economy: Vec<i32>,
+}
+
+impl WorldVariables {
+    pub fn new() -> Self {
+        Self {
+            world: vec![0; WORLD_VARIABLES_COUNT],
+            economy: vec![0; ECONOMY_VARIABLES_COUNT],
+        }
+    }
+}
@@ -13,6 +13,7 @@ struct EventObjectKey {
 
 struct ZoneData {
     monster_spawns_enabled: bool,
+    pvp_enabled: bool,
     event_objects: HashMap<EventObjectKey, Entity>,
 }
 
@@ -34,6 +35,7 @@ impl ZoneList {
             zone_id,
             ZoneData {
                 monster_spawns_enabled: true,
+                pvp_enabled: false,
                 event_objects: Default::default(),
             },
         );
@@ -55,6 +57,22 @@ impl ZoneList {
         }
     }
 
+    pub fn get_pvp_enabled(&self, zone_id: ZoneId) -> bool {
+        self.zones
+            .get(&zone_id)
+            .map(|zone| zone.pvp_enabled)
+            .unwrap_or(false)
+    }
+
+    pub fn set_pvp_enabled(&mut self, zone_id: ZoneId, enabled: bool) -> bool {
+        if let Some(zone) = self.zones.get_mut(&zone_id) {
+            zone.pvp_enabled = enabled;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn add_event_object(
         &mut self,
         zone_id: ZoneId,
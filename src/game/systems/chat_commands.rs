@@ -20,26 +20,29 @@ use crate::{
             CharacterBundle,
         },
         components::{
-            AbilityValues, BasicStats, BotAi, BotAiState, ClientEntity, ClientEntityType, Command,
-            EquipmentIndex, EquipmentItemDatabase, GameClient, Inventory, Level, Money, MoveMode,
-            MoveSpeed, NextCommand, Owner, PassiveRecoveryTime, PersonalStore, Position,
-            SkillPoints, Stamina, StatPoints, StatusEffects, Team, UnionMembership,
+            AbilityValues, BasicStats, BotAi, BotAiState, CharacterInfo, ClientEntity,
+            ClientEntityType, Command, Cooldowns, EquipmentIndex, EquipmentItemDatabase,
+            GameClient, Inventory, InventoryPageType, ItemSlot, Level, Money, MoveMode, MoveSpeed,
+            NextCommand, NpcAi, Owner, PassiveRecoveryTime, PersonalStore, Position, SkillPoints,
+            Stamina, StatPoints, StatusEffects, Team, UnionMembership, INVENTORY_PAGE_SIZE,
             PERSONAL_STORE_ITEM_SLOTS,
         },
-        events::{ChatCommandEvent, RewardXpEvent},
+        events::{ChatCommandEvent, MailEvent, MailEventSend, RewardXpEvent},
         messages::server::{ServerMessage, UpdateSpeed, Whisper},
         resources::{BotList, BotListEntry, ClientEntityList, ServerMessages},
         GameData,
     },
 };
 
-pub struct ChatCommandWorld<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k, 'l> {
+pub struct ChatCommandWorld<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k, 'l, 'm, 'n, 'o, 'p> {
     commands: &'a mut Commands<'b>,
     bot_list: &'c mut ResMut<'d, BotList>,
     client_entity_list: &'e mut ResMut<'f, ClientEntityList>,
     game_data: &'g Res<'h, GameData>,
-    reward_xp_events: &'i mut EventWriter<'j, RewardXpEvent>,
-    server_messages: &'k mut ResMut<'l, ServerMessages>,
+    mail_events: &'i mut EventWriter<'j, MailEvent>,
+    reward_xp_events: &'k mut EventWriter<'l, RewardXpEvent>,
+    server_messages: &'m mut ResMut<'n, ServerMessages>,
+    npc_ai_query: Query<'o, &'p mut NpcAi>,
 }
 
 pub struct ChatCommandUser<'world, 'a> {
@@ -50,6 +53,7 @@ pub struct ChatCommandUser<'world, 'a> {
     level: &'world Level,
     position: &'world Position,
     basic_stats: &'a mut Mut<'world, BasicStats>,
+    character_info: &'a mut Mut<'world, CharacterInfo>,
     inventory: &'a mut Mut<'world, Inventory>,
     skill_points: &'a mut Mut<'world, SkillPoints>,
     stamina: &'a mut Mut<'world, Stamina>,
@@ -79,6 +83,16 @@ lazy_static! {
                     .arg(Arg::new("value").required(true)),
             )
             .subcommand(App::new("speed").arg(Arg::new("speed").required(true)))
+            .subcommand(
+                App::new("mail")
+                    .arg(Arg::new("name").required(true))
+                    .arg(Arg::new("text").required(true))
+                    .arg(Arg::new("item").long("item").takes_value(true))
+                    .arg(Arg::new("quantity").long("quantity").takes_value(true))
+                    .arg(Arg::new("money").long("money").takes_value(true)),
+            )
+            .subcommand(App::new("ai_trace"))
+            .subcommand(App::new("save_point").arg(Arg::new("reset")))
     };
 }
 
@@ -150,6 +164,28 @@ impl From<ParseFloatError> for ChatCommandError {
     }
 }
 
+// Parses an inventory slot in the form page:slot, e.g. consumables:1
+fn parse_inventory_slot(str: &str) -> Result<ItemSlot, ChatCommandError> {
+    let mut split = str.splitn(2, ':');
+    let page_type = match split.next() {
+        Some("equipment") => InventoryPageType::Equipment,
+        Some("consumables") => InventoryPageType::Consumables,
+        Some("materials") => InventoryPageType::Materials,
+        Some("vehicles") => InventoryPageType::Vehicles,
+        _ => return Err(ChatCommandError::InvalidArguments),
+    };
+    let slot = split
+        .next()
+        .ok_or(ChatCommandError::InvalidArguments)?
+        .parse::<usize>()?;
+
+    if slot == 0 || slot > INVENTORY_PAGE_SIZE {
+        return Err(ChatCommandError::InvalidArguments);
+    }
+
+    Ok(ItemSlot::Inventory(page_type, slot - 1))
+}
+
 fn create_bot_entity(
     chat_command_world: &mut ChatCommandWorld,
     name: String,
@@ -206,13 +242,16 @@ fn create_bot_entity(
             ability_values,
             basic_stats: bot_data.basic_stats,
             command: Command::default(),
+            cooldowns: Cooldowns::new(),
             equipment: bot_data.equipment,
             experience_points: bot_data.experience_points,
+            friend_list: bot_data.friend_list,
             health_points: bot_data.health_points,
             hotbar: bot_data.hotbar,
             info: bot_data.info,
             inventory: bot_data.inventory,
             level: bot_data.level,
+            mailbox: bot_data.mailbox,
             mana_points: bot_data.mana_points,
             motion_data,
             move_mode: MoveMode::Run,
@@ -526,6 +565,96 @@ fn handle_chat_command(
                 }),
             );
         }
+        ("mail", arg_matches) => {
+            let item_slot = arg_matches
+                .value_of("item")
+                .map(parse_inventory_slot)
+                .transpose()?;
+            let item_quantity = arg_matches
+                .value_of("quantity")
+                .map(|quantity| quantity.parse::<u32>())
+                .transpose()?;
+            let money = arg_matches
+                .value_of("money")
+                .map(|money| money.parse::<i64>().map(Money))
+                .transpose()?;
+
+            chat_command_world
+                .mail_events
+                .send(MailEvent::Send(MailEventSend {
+                    sender_entity: chat_command_user.entity,
+                    recipient_name: arg_matches.value_of("name").unwrap().to_string(),
+                    text: arg_matches.value_of("text").unwrap().to_string(),
+                    item_slot,
+                    item_quantity,
+                    money,
+                }));
+        }
+        ("ai_trace", _) => {
+            let user_position = chat_command_user.position.position.xy();
+            let nearest_npc = chat_command_world
+                .client_entity_list
+                .get_zone(chat_command_user.position.zone_id)
+                .and_then(|client_entity_zone| {
+                    client_entity_zone
+                        .iter_entities_within_distance(user_position, 1000.0)
+                        .filter(|(entity, _)| chat_command_world.npc_ai_query.get(*entity).is_ok())
+                        .min_by(|(_, a), (_, b)| {
+                            let distance_a = (a.xy() - user_position).magnitude_squared();
+                            let distance_b = (b.xy() - user_position).magnitude_squared();
+                            distance_a.partial_cmp(&distance_b).unwrap()
+                        })
+                });
+
+            if let Some((npc_entity, _)) = nearest_npc {
+                if let Ok(mut npc_ai) = chat_command_world.npc_ai_query.get_mut(npc_entity) {
+                    npc_ai.is_trace_enabled = !npc_ai.is_trace_enabled;
+                    send_multiline_whisper(
+                        chat_command_user.game_client,
+                        &format!(
+                            "AI trace {} for {:?} (ai {})",
+                            if npc_ai.is_trace_enabled {
+                                "enabled"
+                            } else {
+                                "disabled"
+                            },
+                            npc_entity,
+                            npc_ai.ai_index
+                        ),
+                    );
+                }
+            } else {
+                send_multiline_whisper(chat_command_user.game_client, "No NPC with AI nearby");
+            }
+        }
+        ("save_point", arg_matches) => {
+            if let Some(reset) = arg_matches.value_of("reset") {
+                if reset != "reset" {
+                    return Err(ChatCommandError::InvalidArguments);
+                }
+
+                let position = chat_command_user.position;
+                let revive_position = chat_command_world
+                    .game_data
+                    .zones
+                    .get_zone(position.zone_id)
+                    .and_then(|zone_data| zone_data.get_closest_revive_position(position.position))
+                    .unwrap_or(position.position);
+                chat_command_user.character_info.revive_zone_id = position.zone_id;
+                chat_command_user.character_info.revive_position = revive_position;
+            }
+
+            send_multiline_whisper(
+                chat_command_user.game_client,
+                &format!(
+                    "save point zone: {} position: ({}, {}, {})",
+                    chat_command_user.character_info.revive_zone_id.get(),
+                    chat_command_user.character_info.revive_position.x,
+                    chat_command_user.character_info.revive_position.y,
+                    chat_command_user.character_info.revive_position.z,
+                ),
+            );
+        }
         _ => return Err(ChatCommandError::InvalidCommand),
     }
 
@@ -541,16 +670,19 @@ pub fn chat_commands_system(
         &Level,
         &Position,
         &mut BasicStats,
+        &mut CharacterInfo,
         &mut Inventory,
         &mut SkillPoints,
         &mut Stamina,
         &mut StatPoints,
         &mut UnionMembership,
     )>,
+    npc_ai_query: Query<&mut NpcAi>,
     mut bot_list: ResMut<BotList>,
     mut client_entity_list: ResMut<ClientEntityList>,
     game_data: Res<GameData>,
     mut chat_command_events: EventReader<ChatCommandEvent>,
+    mut mail_events: EventWriter<MailEvent>,
     mut reward_xp_events: EventWriter<RewardXpEvent>,
     mut server_messages: ResMut<ServerMessages>,
 ) {
@@ -559,8 +691,10 @@ pub fn chat_commands_system(
         bot_list: &mut bot_list,
         client_entity_list: &mut client_entity_list,
         game_data: &game_data,
+        mail_events: &mut mail_events,
         reward_xp_events: &mut reward_xp_events,
         server_messages: &mut server_messages,
+        npc_ai_query,
     };
 
     for &ChatCommandEvent {
@@ -575,6 +709,7 @@ pub fn chat_commands_system(
             level,
             position,
             mut basic_stats,
+            mut character_info,
             mut inventory,
             mut skill_points,
             mut stamina,
@@ -590,6 +725,7 @@ pub fn chat_commands_system(
                 level,
                 position,
                 basic_stats: &mut basic_stats,
+                character_info: &mut character_info,
                 inventory: &mut inventory,
                 skill_points: &mut skill_points,
                 stamina: &mut stamina,
@@ -20,8 +20,10 @@ pub fn client_entity_visibility_system(
         &GameClient,
         &ClientEntity,
         &Position,
+        &Team,
     )>,
     entity_id_query: Query<&ClientEntity>,
+    invisible_query: Query<(&StatusEffects, &Team)>,
     characters_query: Query<(
         &AbilityValues,
         &CharacterInfo,
@@ -73,14 +75,31 @@ pub fn client_entity_visibility_system(
             visibility_game_client,
             visibility_client_entity,
             visibility_position,
+            visibility_team,
         )| {
             if let Some(client_entity_zone) =
                 client_entity_list.get_zone(visibility_position.zone_id)
             {
-                let sector_visible_entities =
-                    client_entity_zone.get_sector_visible_entities(visibility_client_entity.sector);
+                let mut sector_visible_entities = *client_entity_zone
+                    .get_sector_visible_entities(visibility_client_entity.sector);
+
+                // Invisible entities are hidden from anyone not on their team
+                let hidden_entity_indices: Vec<usize> = sector_visible_entities
+                    .iter_ones()
+                    .filter(|index| {
+                        client_entity_zone
+                            .get_entity(ClientEntityId(*index))
+                            .and_then(|(entity, ..)| invisible_query.get(*entity).ok())
+                            .map_or(false, |(status_effects, team)| {
+                                team.id != visibility_team.id && status_effects.is_invisible()
+                            })
+                    })
+                    .collect();
+                for index in hidden_entity_indices {
+                    sector_visible_entities.set(index, false);
+                }
 
-                let mut visibility_difference = visibility.entities ^ *sector_visible_entities;
+                let mut visibility_difference = visibility.entities ^ sector_visible_entities;
 
                 // Ignore self
                 visibility_difference.set(visibility_client_entity.id.0, false);
@@ -279,7 +298,7 @@ pub fn client_entity_visibility_system(
                 }
 
                 // Update visibility
-                visibility.entities = *sector_visible_entities;
+                visibility.entities = sector_visible_entities;
             }
         },
     );
@@ -1,20 +1,24 @@
 use std::time::Duration;
 
-use bevy_ecs::prelude::{Commands, Entity, EventWriter, Mut, Query, Res, ResMut};
+use bevy_ecs::prelude::{Commands, Entity, EventWriter, Local, Mut, Query, Res, ResMut};
 use nalgebra::Point3;
 
 use crate::{
-    data::item::{Item, ItemClass, StackableSlotBehaviour},
+    data::{
+        item::{Item, ItemClass, StackableSlotBehaviour},
+        MotionId,
+    },
     game::{
         bundles::client_entity_leave_zone,
         components::{
             AbilityValues, AmmoIndex, ClientEntity, ClientEntityType, Command, CommandAttack,
             CommandCastSkill, CommandCastSkillTarget, CommandData, CommandMove,
-            CommandPickupDroppedItem, CommandSit, CommandStop, Destination, DroppedItem, Equipment,
-            EquipmentIndex, GameClient, HealthPoints, Inventory, ItemSlot, MotionData, MoveMode,
-            MoveSpeed, NextCommand, Npc, Owner, PersonalStore, Position, Target, OwnedExpireTime,
+            CommandPickupDroppedItem, CommandSit, CommandStop, Cooldowns, Destination, DroppedItem,
+            Equipment, EquipmentIndex, GameClient, HealthPoints, Inventory, ItemSlot, MotionData,
+            MoveMode, MoveSpeed, NextCommand, Npc, OwnedExpireTime, Owner, PersonalStore, Position,
+            StatusEffects, Target,
         },
-        events::{DamageEvent, SkillEvent, SkillEventTarget},
+        events::{DamageEvent, SkillCastInterruptedEvent, SkillEvent, SkillEventTarget},
         messages::server::{
             self, PickupDroppedItemContent, PickupDroppedItemError, PickupDroppedItemResult,
             ServerMessage,
@@ -158,6 +162,8 @@ pub fn command_system(
         Option<&mut Inventory>,
         Option<&Npc>,
         Option<&PersonalStore>,
+        Option<&StatusEffects>,
+        Option<&mut Cooldowns>,
     )>,
     move_target_query: Query<(&ClientEntity, &Position)>,
     attack_target_query: Query<(&ClientEntity, &Position, &AbilityValues, &HealthPoints)>,
@@ -172,8 +178,10 @@ pub fn command_system(
     mut client_entity_list: ResMut<ClientEntityList>,
     mut damage_events: EventWriter<DamageEvent>,
     mut skill_events: EventWriter<SkillEvent>,
+    mut skill_cast_interrupted_events: EventWriter<SkillCastInterruptedEvent>,
     mut server_messages: ResMut<ServerMessages>,
     game_data: Res<GameData>,
+    mut next_cast_id: Local<u32>,
 ) {
     query.for_each_mut(
         |(
@@ -190,7 +198,77 @@ pub fn command_system(
             inventory,
             npc,
             personal_store,
+            status_effects,
+            mut cooldowns,
         )| {
+            if let Some(status_effects) = status_effects {
+                if !matches!(command.command, CommandData::Die(_)) {
+                    if status_effects.is_disabled() {
+                        // Stunned or sleeping entities must stop whatever they are doing
+                        if let CommandData::CastSkill(CommandCastSkill { cast_id, .. }) =
+                            command.command
+                        {
+                            skill_cast_interrupted_events
+                                .send(SkillCastInterruptedEvent::new(entity, cast_id));
+                        }
+
+                        if !matches!(command.command, CommandData::Stop(_)) {
+                            command_stop(
+                                &mut commands,
+                                &mut command,
+                                entity,
+                                client_entity,
+                                position,
+                                Some(&mut server_messages),
+                            );
+                        } else {
+                            command.duration += server_time.delta;
+                        }
+
+                        if next_command.command.is_some() {
+                            *next_command = NextCommand::default();
+                        }
+                        return;
+                    }
+
+                    if status_effects.is_silenced()
+                        && matches!(next_command.command, Some(CommandData::CastSkill(_)))
+                    {
+                        // Silenced entities can not cast skills
+                        *next_command = NextCommand::with_stop(true);
+                    }
+                }
+            }
+
+            if let Some(cooldowns) = cooldowns.as_ref() {
+                let cooldown_remaining = match next_command.command {
+                    Some(CommandData::CastSkill(CommandCastSkill { skill_id, .. })) => {
+                        game_data.skills.get_skill(skill_id).and_then(|skill_data| {
+                            cooldowns.get_skill_remaining(skill_data, server_time.now)
+                        })
+                    }
+                    _ => None,
+                };
+
+                if let Some(cooldown_remaining) = cooldown_remaining {
+                    // Skill is still on cooldown, reject the cast
+                    if let Some(game_client) = game_client {
+                        game_client
+                            .server_message_tx
+                            .send(ServerMessage::Whisper(server::Whisper {
+                                from: String::from("SERVER"),
+                                text: format!(
+                                    "You must wait {} seconds before using this skill again",
+                                    cooldown_remaining.as_secs() + 1
+                                ),
+                            }))
+                            .ok();
+                    }
+
+                    *next_command = NextCommand::with_stop(true);
+                }
+            }
+
             if !next_command.has_sent_server_message && next_command.command.is_some() {
                 // Send any server message required for update client next command
                 match next_command.command.as_mut().unwrap() {
@@ -259,6 +337,7 @@ pub fn command_system(
                     CommandData::CastSkill(CommandCastSkill {
                         skill_id,
                         skill_target: None,
+                        npc_motion_id,
                         ..
                     }) => {
                         server_messages.send_entity_message(
@@ -266,13 +345,14 @@ pub fn command_system(
                             ServerMessage::CastSkillSelf(server::CastSkillSelf {
                                 entity_id: client_entity.id,
                                 skill_id: *skill_id,
-                                npc_motion_id: None, // TODO: CastSkillSelf npc_motion_id
+                                npc_motion_id: *npc_motion_id,
                             }),
                         );
                     }
                     CommandData::CastSkill(CommandCastSkill {
                         skill_id,
                         skill_target: Some(CommandCastSkillTarget::Entity(target_entity)),
+                        npc_motion_id,
                         ..
                     }) => {
                         if let Some((target_client_entity, target_position, ..)) =
@@ -290,7 +370,7 @@ pub fn command_system(
                                         target_entity_id: target_client_entity.id,
                                         target_distance: distance,
                                         target_position: target_position.position.xy(),
-                                        npc_motion_id: None, // TODO: CastSkillTargetEntity npc_motion_id
+                                        npc_motion_id: *npc_motion_id,
                                     },
                                 ),
                             );
@@ -301,6 +381,7 @@ pub fn command_system(
                     CommandData::CastSkill(CommandCastSkill {
                         skill_id,
                         skill_target: Some(CommandCastSkillTarget::Position(target_position)),
+                        npc_motion_id,
                         ..
                     }) => {
                         server_messages.send_entity_message(
@@ -310,7 +391,7 @@ pub fn command_system(
                                     entity_id: client_entity.id,
                                     skill_id: *skill_id,
                                     target_position: *target_position,
-                                    npc_motion_id: None, // TODO: CastSkillTargetPosition npc_motion_id
+                                    npc_motion_id: *npc_motion_id,
                                 },
                             ),
                         );
@@ -703,6 +784,8 @@ pub fn command_system(
                     skill_id,
                     skill_target,
                     ref use_item,
+                    npc_motion_id,
+                    ..
                 }) => {
                     if let Some(skill_data) = game_data.skills.get_skill(skill_id) {
                         let mut entity_commands = commands.entity(entity);
@@ -745,18 +828,29 @@ pub fn command_system(
                                 .unwrap_or_else(|| Duration::from_secs(0))
                                 .mul_f32(skill_data.casting_motion_speed);
 
-                            let action_duration = skill_data
-                                .action_motion_id
-                                .and_then(|motion_id| {
-                                    if let Some(npc) = npc {
-                                        game_data.npcs.get_npc_motion(npc.id, motion_id)
-                                    } else {
-                                        game_data.motions.find_first_character_motion(motion_id)
-                                    }
-                                })
-                                .map(|motion_data| motion_data.duration)
-                                .unwrap_or_else(|| Duration::from_secs(0))
-                                .mul_f32(skill_data.action_motion_speed);
+                            let action_duration = if let (Some(npc), Some(npc_motion_id)) =
+                                (npc, npc_motion_id)
+                            {
+                                // NPCs using skills from AI play their own motion instead
+                                game_data
+                                    .npcs
+                                    .get_npc_motion(npc.id, MotionId::new(npc_motion_id as u16))
+                                    .map(|motion_data| motion_data.duration)
+                                    .unwrap_or_else(|| Duration::from_secs(0))
+                            } else {
+                                skill_data
+                                    .action_motion_id
+                                    .and_then(|motion_id| {
+                                        if let Some(npc) = npc {
+                                            game_data.npcs.get_npc_motion(npc.id, motion_id)
+                                        } else {
+                                            game_data.motions.find_first_character_motion(motion_id)
+                                        }
+                                    })
+                                    .map(|motion_data| motion_data.duration)
+                                    .unwrap_or_else(|| Duration::from_secs(0))
+                                    .mul_f32(skill_data.action_motion_speed)
+                            };
 
                             // For skills which target an entity, we must send a message indicating start of skill
                             if target_entity.is_some() {
@@ -767,8 +861,11 @@ pub fn command_system(
                             }
 
                             // Send skill event for effect to be applied after casting motion
+                            let cast_id = *next_cast_id;
+                            *next_cast_id = next_cast_id.wrapping_add(1);
                             skill_events.send(SkillEvent::new(
                                 entity,
+                                cast_id,
                                 server_time.now + casting_duration,
                                 skill_id,
                                 match skill_target {
@@ -783,10 +880,17 @@ pub fn command_system(
                                 use_item.clone(),
                             ));
 
+                            // Cooldown starts once the skill begins casting
+                            if let Some(cooldowns) = cooldowns.as_mut() {
+                                cooldowns.set_skill_cooldown(skill_data, server_time.now);
+                            }
+
                             // Set current command to cast skill
                             *command = Command::with_cast_skill(
                                 skill_id,
                                 skill_target,
+                                npc_motion_id,
+                                cast_id,
                                 casting_duration,
                                 action_duration,
                             );
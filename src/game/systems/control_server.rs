@@ -2,7 +2,7 @@ use bevy_ecs::prelude::{Commands, EventWriter, Res, ResMut};
 
 use crate::game::{
     components::{GameClient, LoginClient, ServerInfo, WorldClient},
-    events::SaveEvent,
+    events::{MessengerEvent, SaveEvent},
     messages::control::{ClientType, ControlMessage},
     resources::{ControlChannel, GameServer, ServerList, WorldServer},
 };
@@ -11,6 +11,7 @@ pub fn control_server_system(
     mut commands: Commands,
     channel: Res<ControlChannel>,
     mut server_list: ResMut<ServerList>,
+    mut messenger_events: EventWriter<MessengerEvent>,
     mut save_events: EventWriter<SaveEvent>,
 ) {
     while let Ok(message) = channel.control_rx.try_recv() {
@@ -41,7 +42,10 @@ pub fn control_server_system(
                 client_type,
                 entity,
             } => match client_type {
-                ClientType::Game => save_events.send(SaveEvent::with_character(entity, true)),
+                ClientType::Game => {
+                    messenger_events.send(MessengerEvent::Logout(entity));
+                    save_events.send(SaveEvent::with_character(entity, true));
+                }
                 _ => commands.entity(entity).despawn(),
             },
             ControlMessage::AddWorldServer {
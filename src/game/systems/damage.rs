@@ -1,35 +1,59 @@
 use std::time::Duration;
 
-use bevy_ecs::prelude::{Commands, EventReader, Query, Res, ResMut};
+use bevy_ecs::prelude::{Commands, EventReader, EventWriter, Query, Res, ResMut};
 
 use crate::{
-    data::Damage,
+    data::{Damage, StatusEffectType},
     game::{
+        bundles::experience_points_apply_penalty,
         components::{
-            ClientEntity, Command, DamageSource, DamageSources, HealthPoints, MotionData, NpcAi,
+            ClientEntity, Command, CommandCastSkill, CommandData, DamageSource, DamageSources,
+            DeathXpPenalty, Destination, ExperiencePoints, GameClient, HealthPoints, Level,
+            MotionData, Npc, NpcAi, Position, Stamina, StatusEffects,
         },
-        events::{DamageEvent, DamageEventAttack, DamageEventSkill, DamageEventTagged},
-        messages::server::{DamageEntity, ServerMessage},
-        resources::{ServerMessages, ServerTime},
+        events::{
+            DamageEvent, DamageEventAttack, DamageEventKill, DamageEventSkill,
+            DamageEventStatusEffect, DamageEventTagged, SkillCastInterruptedEvent,
+        },
+        messages::server::{
+            CancelCastingSkillReason, DamageEntity, ServerMessage, UpdateStatusEffects,
+            UpdateXpStamina,
+        },
+        resources::{GameConfig, HitStunDamageSource, ServerMessages, ServerTime, ZoneList},
+        GameData,
     },
 };
 
 pub fn damage_system(
     mut commands: Commands,
-    attacker_query: Query<&ClientEntity>,
+    attacker_query: Query<(&ClientEntity, Option<&Npc>)>,
     mut defender_query: Query<(
         &ClientEntity,
         &mut HealthPoints,
         Option<&mut DamageSources>,
         Option<&mut NpcAi>,
         Option<&MotionData>,
+        Option<&Command>,
+        Option<&Npc>,
+        (
+            &Position,
+            Option<&Level>,
+            Option<&mut ExperiencePoints>,
+            Option<&Stamina>,
+            Option<&GameClient>,
+            Option<&mut StatusEffects>,
+        ),
     )>,
     mut damage_events: EventReader<DamageEvent>,
+    mut skill_cast_interrupted_events: EventWriter<SkillCastInterruptedEvent>,
     mut server_messages: ResMut<ServerMessages>,
+    game_config: Res<GameConfig>,
+    game_data: Res<GameData>,
     server_time: Res<ServerTime>,
+    zone_list: Res<ZoneList>,
 ) {
     for damage_event in damage_events.iter() {
-        let (attacker_entity, defender_entity, damage, from_skill) = match *damage_event {
+        let (attacker_entity, defender_entity, mut damage, from_skill) = match *damage_event {
             DamageEvent::Attack(DamageEventAttack {
                 attacker: attacker_entity,
                 defender: defender_entity,
@@ -60,27 +84,92 @@ pub fn damage_system(
                 },
                 None,
             ),
+            DamageEvent::Kill(DamageEventKill {
+                attacker: attacker_entity,
+                defender: defender_entity,
+            }) => (
+                attacker_entity,
+                defender_entity,
+                Damage {
+                    amount: 0,
+                    is_critical: false,
+                    apply_hit_stun: false,
+                },
+                None,
+            ),
+            DamageEvent::StatusEffect(DamageEventStatusEffect {
+                defender: defender_entity,
+                damage,
+            }) => (defender_entity, defender_entity, damage, None),
         };
+        let is_self_damage = matches!(
+            damage_event,
+            DamageEvent::Kill(_) | DamageEvent::StatusEffect(_)
+        ) && attacker_entity == defender_entity;
 
-        let attacker_entity_id = attacker_query
+        let (attacker_entity_id, is_attacker_npc) = attacker_query
             .get(attacker_entity)
-            .map(|client_entity| Some(client_entity.id))
-            .unwrap_or(None);
+            .map(|(client_entity, npc)| (Some(client_entity.id), npc.is_some()))
+            .unwrap_or((None, false));
 
-        if let Ok((client_entity, mut health_points, damage_sources, npc_ai, motion_data)) =
-            defender_query.get_mut(defender_entity)
+        if let Ok((
+            client_entity,
+            mut health_points,
+            damage_sources,
+            npc_ai,
+            motion_data,
+            command,
+            npc,
+            (position, level, experience_points, stamina, game_client, status_effects),
+        )) = defender_query.get_mut(defender_entity)
         {
-            if damage.apply_hit_stun {
-                // TODO: Apply hit stun by setting next command to HitStun ?
-            }
-
             if health_points.hp == 0 {
                 // Entity already dead, ignore any further damage
                 continue;
             }
 
+            if matches!(damage_event, DamageEvent::Kill(_)) {
+                damage.amount = health_points.hp;
+            }
+
             health_points.hp = health_points.hp.saturating_sub(damage.amount as u32);
 
+            let hit_stun_damage_source = match damage_event {
+                DamageEvent::Attack(_) => Some(HitStunDamageSource::Attack),
+                DamageEvent::Skill(_) => Some(HitStunDamageSource::Skill),
+                _ => None,
+            };
+            let is_hit_stun_source = hit_stun_damage_source.map_or(false, |source| {
+                game_config.hit_stun_damage_sources.contains(&source)
+            });
+            let is_hit_stun_immune = npc.map_or(false, |npc| {
+                game_config.hit_stun_immune_npc_ids.contains(&npc.id)
+            });
+            if !is_hit_stun_source || is_hit_stun_immune {
+                // Clear hit stun so clients do not play the hit motion either
+                damage.apply_hit_stun = false;
+            }
+
+            if let Some(mut status_effects) = status_effects {
+                if damage.amount > 0
+                    && health_points.hp > 0
+                    && status_effects.active[StatusEffectType::Sleep].is_some()
+                {
+                    // Taking damage wakes us up from sleep
+                    status_effects.active[StatusEffectType::Sleep] = None;
+
+                    server_messages.send_entity_message(
+                        client_entity,
+                        ServerMessage::UpdateStatusEffects(UpdateStatusEffects {
+                            entity_id: client_entity.id,
+                            status_effects: status_effects.clone(),
+                            updated_hp: None,
+                            updated_mp: None,
+                        }),
+                    );
+                }
+            }
+
             if !matches!(damage_event, DamageEvent::Tagged(_)) {
                 if let Some(attacker_entity_id) = attacker_entity_id {
                     server_messages.send_entity_message(
@@ -96,7 +185,41 @@ pub fn damage_system(
                 }
             }
 
-            if let Some(mut damage_sources) = damage_sources {
+            if damage.apply_hit_stun && health_points.hp > 0 {
+                if let (Some(command), Some(hit_motion)) = (
+                    command,
+                    motion_data.and_then(|motion_data| motion_data.get_hit()),
+                ) {
+                    // Hit stun interrupts movement and casting for the duration of the hit motion
+                    if matches!(
+                        command.command,
+                        CommandData::Move(_) | CommandData::CastSkill(_)
+                    ) {
+                        if let CommandData::CastSkill(CommandCastSkill { cast_id, .. }) =
+                            command.command
+                        {
+                            skill_cast_interrupted_events
+                                .send(SkillCastInterruptedEvent::new(defender_entity, cast_id));
+                            server_messages.send_entity_message(
+                                client_entity,
+                                ServerMessage::CancelCastingSkill(
+                                    client_entity.id,
+                                    CancelCastingSkillReason::Interrupted,
+                                ),
+                            );
+                        }
+
+                        commands
+                            .entity(defender_entity)
+                            .remove::<Destination>()
+                            .insert(Command::with_hit_stun(hit_motion.duration));
+                    }
+                }
+            }
+
+            if is_self_damage {
+                // Damaging ourself should not count as damage from an attacker
+            } else if let Some(mut damage_sources) = damage_sources {
                 if let Some(mut source) = damage_sources
                     .damage_sources
                     .iter_mut()
@@ -134,18 +257,62 @@ pub fn damage_system(
             }
 
             if let Some(mut npc_ai) = npc_ai {
-                npc_ai.pending_damage.push((attacker_entity, damage));
+                if !is_self_damage {
+                    npc_ai.pending_damage.push((attacker_entity, damage));
+                }
             }
 
             if health_points.hp == 0 {
+                if let Some(&Command {
+                    command: CommandData::CastSkill(CommandCastSkill { cast_id, .. }),
+                    ..
+                }) = command
+                {
+                    skill_cast_interrupted_events
+                        .send(SkillCastInterruptedEvent::new(defender_entity, cast_id));
+                }
+
                 commands.entity(defender_entity).insert(Command::with_die(
-                    Some(attacker_entity),
+                    if is_self_damage {
+                        None
+                    } else {
+                        Some(attacker_entity)
+                    },
                     Some(damage),
                     motion_data
                         .and_then(|motion_data| motion_data.get_die())
                         .map(|die_motion| die_motion.duration)
                         .or_else(|| Some(Duration::from_secs(1))),
                 ));
+
+                // Characters killed by a monster outside of PvP zones lose xp
+                if let (Some(game_client), Some(level), Some(mut experience_points)) =
+                    (game_client, level, experience_points)
+                {
+                    if !is_self_damage
+                        && is_attacker_npc
+                        && !zone_list.get_pvp_enabled(position.zone_id)
+                    {
+                        let penalty_xp = experience_points_apply_penalty(
+                            game_data.ability_value_calculator.as_ref(),
+                            level,
+                            &mut experience_points,
+                            game_config.death_xp_penalty_percent,
+                        );
+                        commands
+                            .entity(defender_entity)
+                            .insert(DeathXpPenalty::new(penalty_xp));
+
+                        game_client
+                            .server_message_tx
+                            .send(ServerMessage::UpdateXpStamina(UpdateXpStamina {
+                                xp: experience_points.xp,
+                                stamina: stamina.map_or(0, |stamina| stamina.stamina),
+                                source_entity_id: None,
+                            }))
+                            .ok();
+                    }
+                }
             }
         }
     }
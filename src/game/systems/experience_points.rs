@@ -1,4 +1,4 @@
-use bevy_ecs::prelude::{Entity, EventReader, Query, Res, ResMut};
+use bevy_ecs::prelude::{Entity, EventReader, EventWriter, Query, Res, ResMut};
 
 use crate::game::{
     components::{
@@ -6,9 +6,9 @@ use crate::game::{
         HealthPoints, Level, ManaPoints, SkillList, SkillPoints, Stamina, StatPoints,
         StatusEffects, MAX_STAMINA,
     },
-    events::RewardXpEvent,
+    events::{QuestTriggerEvent, RewardXpEvent},
     messages::server::{ServerMessage, UpdateLevel, UpdateXpStamina},
-    resources::ServerMessages,
+    resources::{GameConfig, ServerMessages},
     GameData,
 };
 
@@ -33,8 +33,10 @@ pub fn experience_points_system(
         &StatusEffects,
     )>,
     source_entity_query: Query<&ClientEntity>,
+    game_config: Res<GameConfig>,
     game_data: Res<GameData>,
     mut reward_xp_events: EventReader<RewardXpEvent>,
+    mut quest_trigger_events: EventWriter<QuestTriggerEvent>,
     mut server_messages: ResMut<ServerMessages>,
 ) {
     for reward_xp_event in reward_xp_events.iter() {
@@ -60,11 +62,8 @@ pub fn experience_points_system(
                 stamina.stamina = MAX_STAMINA;
             }
 
-            // TODO: Apply level cap
-            // TODO: Penalty xp?
-
             let level_before = level.level;
-            loop {
+            while level.level < game_config.max_character_level {
                 let need_xp = game_data
                     .ability_value_calculator
                     .calculate_levelup_require_xp(level.level);
@@ -84,8 +83,24 @@ pub fn experience_points_system(
                     .calculate_levelup_reward_stat_points(level.level);
             }
 
+            if level.level >= game_config.max_character_level {
+                // Do not accumulate xp beyond what is required for the next level at the cap
+                let max_xp = game_data
+                    .ability_value_calculator
+                    .calculate_levelup_require_xp(level.level)
+                    .saturating_sub(1);
+                if experience_points.xp > max_xp {
+                    experience_points.xp = max_xp;
+                }
+            }
+
             if level.level != level_before {
-                // TODO: Call level up quest trigger
+                for _ in level_before..level.level {
+                    quest_trigger_events.send(QuestTriggerEvent {
+                        trigger_entity: entity,
+                        trigger_hash: "levelup".into(),
+                    });
+                }
 
                 // Update ability values and restore hp / mp
                 if let Ok((
@@ -142,3 +157,157 @@ pub fn experience_points_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        event::Events,
+        prelude::{IntoSystem, World},
+        schedule::{Stage, SystemStage},
+    };
+    use nalgebra::Point2;
+
+    use super::*;
+    use crate::game::{
+        components::{ClientEntityId, ClientEntityType},
+        test_utils::{test_game_data, test_zone_id, TestAbilityValueCalculator},
+    };
+
+    fn create_world(max_character_level: u32) -> World {
+        let mut game_config = GameConfig::new();
+        game_config.max_character_level = max_character_level;
+
+        let mut world = World::new();
+        world.insert_resource(game_config);
+        world.insert_resource(test_game_data());
+        world.insert_resource(ServerMessages::new());
+        world.insert_resource(Events::<RewardXpEvent>::default());
+        world.insert_resource(Events::<QuestTriggerEvent>::default());
+        world
+    }
+
+    fn spawn_character(world: &mut World, level: u32, xp: u64) -> Entity {
+        let mut experience_points = ExperiencePoints::new();
+        experience_points.xp = xp;
+
+        world
+            .spawn()
+            .insert_bundle((
+                ClientEntity::new(
+                    ClientEntityType::Character,
+                    ClientEntityId(1),
+                    test_zone_id(),
+                    Point2::new(0, 0),
+                ),
+                Level::new(level),
+                experience_points,
+                Stamina::new(),
+                SkillPoints::new(),
+                StatPoints::new(),
+            ))
+            .id()
+    }
+
+    fn reward_xp(world: &mut World, entity: Entity, xp: u64) {
+        world
+            .get_resource_mut::<Events<RewardXpEvent>>()
+            .unwrap()
+            .send(RewardXpEvent::new(entity, xp, 0, None));
+
+        let mut stage =
+            SystemStage::single_threaded().with_system(experience_points_system.system());
+        stage.run(world);
+    }
+
+    fn get_level_and_xp(world: &World, entity: Entity) -> (u32, u64) {
+        (
+            world.get::<Level>(entity).unwrap().level,
+            world.get::<ExperiencePoints>(entity).unwrap().xp,
+        )
+    }
+
+    fn count_levelup_triggers(world: &World) -> usize {
+        let events = world.get_resource::<Events<QuestTriggerEvent>>().unwrap();
+        events.get_reader().iter(events).count()
+    }
+
+    #[test]
+    fn gain_single_level() {
+        let mut world = create_world(100);
+        let entity = spawn_character(&mut world, 1, 0);
+
+        reward_xp(&mut world, entity, 150);
+
+        assert_eq!(get_level_and_xp(&world, entity), (2, 50));
+        assert_eq!(world.get::<SkillPoints>(entity).unwrap().points, 1);
+        assert_eq!(world.get::<StatPoints>(entity).unwrap().points, 10);
+        assert_eq!(count_levelup_triggers(&world), 1);
+    }
+
+    #[test]
+    fn gain_multiple_levels() {
+        let mut world = create_world(100);
+        let entity = spawn_character(&mut world, 1, 0);
+        let required_xp = (1..4)
+            .map(TestAbilityValueCalculator::levelup_require_xp)
+            .sum::<u64>();
+
+        reward_xp(&mut world, entity, required_xp + 50);
+
+        assert_eq!(get_level_and_xp(&world, entity), (4, 50));
+        assert_eq!(world.get::<SkillPoints>(entity).unwrap().points, 3);
+        assert_eq!(world.get::<StatPoints>(entity).unwrap().points, 30);
+        assert_eq!(count_levelup_triggers(&world), 3);
+    }
+
+    #[test]
+    fn not_enough_xp_to_level() {
+        let mut world = create_world(100);
+        let entity = spawn_character(&mut world, 10, 100);
+
+        reward_xp(&mut world, entity, 100);
+
+        assert_eq!(get_level_and_xp(&world, entity), (10, 200));
+        assert_eq!(count_levelup_triggers(&world), 0);
+    }
+
+    #[test]
+    fn level_clamped_to_max_level() {
+        let mut world = create_world(5);
+        let entity = spawn_character(&mut world, 4, 0);
+
+        reward_xp(&mut world, entity, 1_000_000);
+
+        // Remaining xp is clamped to just below the requirement for the next level
+        assert_eq!(
+            get_level_and_xp(&world, entity),
+            (5, TestAbilityValueCalculator::levelup_require_xp(5) - 1)
+        );
+        assert_eq!(count_levelup_triggers(&world), 1);
+    }
+
+    #[test]
+    fn xp_gained_at_max_level() {
+        let mut world = create_world(5);
+        let entity = spawn_character(&mut world, 5, 0);
+
+        reward_xp(&mut world, entity, 100);
+
+        assert_eq!(get_level_and_xp(&world, entity), (5, 100));
+        assert_eq!(count_levelup_triggers(&world), 0);
+    }
+
+    #[test]
+    fn xp_clamped_at_max_level() {
+        let mut world = create_world(5);
+        let entity = spawn_character(&mut world, 5, 100);
+
+        reward_xp(&mut world, entity, 1_000_000);
+
+        assert_eq!(
+            get_level_and_xp(&world, entity),
+            (5, TestAbilityValueCalculator::levelup_require_xp(5) - 1)
+        );
+        assert_eq!(count_levelup_triggers(&world), 0);
+    }
+}
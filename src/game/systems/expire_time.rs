@@ -1,8 +1,9 @@
-use bevy_ecs::prelude::{Commands, Entity, Query, Res, ResMut};
+use bevy_ecs::prelude::{Commands, Entity, EventWriter, Query, Res, ResMut};
 
 use crate::game::{
     bundles::client_entity_leave_zone,
     components::{ClientEntity, Command, SpawnExpireTime, Position},
+    events::DamageEvent,
     resources::{ClientEntityList, ServerTime},
 };
 
@@ -16,14 +17,15 @@ pub fn expire_time_system(
         Option<&Command>,
     )>,
     mut client_entity_list: ResMut<ClientEntityList>,
+    mut damage_events: EventWriter<DamageEvent>,
     server_time: Res<ServerTime>,
 ) {
     query.for_each(|(entity, expire_time, position, client_entity, command)| {
         if server_time.now >= expire_time.when {
-            if command.is_some() {
-                commands
-                    .entity(entity)
-                    .insert(Command::with_die(None, None, None));
+            if let Some(command) = command {
+                if !command.is_dead() {
+                    damage_events.send(DamageEvent::with_kill(entity, entity));
+                }
             } else {
                 if let (Some(position), Some(client_entity)) = (position, client_entity) {
                     client_entity_leave_zone(
@@ -1,5 +1,5 @@
 use bevy_ecs::prelude::{Commands, Entity, EventWriter, Query, Res, ResMut, Without};
-use log::warn;
+use log::{error, warn};
 use nalgebra::Point3;
 
 use crate::{
@@ -7,23 +7,26 @@ use crate::{
         account::AccountStorage,
         character::CharacterStorage,
         item::{Item, ItemSlotBehaviour, ItemType, StackError, StackableSlotBehaviour},
+        mail::MailStorage,
     },
     game::{
         bundles::{
             client_entity_join_zone, client_entity_leave_zone, client_entity_teleport_zone,
-            CharacterBundle, DroppedItemBundle,
+            experience_points_apply_penalty, CharacterBundle, DroppedItemBundle,
         },
         components::{
             AbilityValues, BasicStatType, BasicStats, CharacterInfo, ClientEntity,
-            ClientEntityType, ClientEntityVisibility, Command, CommandData, CommandSit,
-            DroppedItem, Equipment, EquipmentIndex, EquipmentItemDatabase, ExperiencePoints,
-            GameClient, HealthPoints, Hotbar, Inventory, ItemSlot, Level, ManaPoints, Money,
-            MoveMode, MoveSpeed, NextCommand, PassiveRecoveryTime, Position, QuestState, SkillList,
-            StatPoints, StatusEffects, Team, WorldClient,
+            ClientEntityType, ClientEntityVisibility, Command, CommandData, CommandSit, Cooldowns,
+            DeathXpPenalty, DroppedItem, Equipment, EquipmentIndex, EquipmentItemDatabase,
+            ExperiencePoints, GameClient, HealthPoints, Hotbar, Inventory, ItemSlot, LastShoutTime,
+            Level, ManaPoints, Money, MoveMode, MoveSpeed, NextCommand, PassiveRecoveryTime,
+            Position, QuestState, SkillList, Stamina, StatPoints, StatusEffects, Team, WorldClient,
+            SHOUT_COOLDOWN_DURATION,
         },
         events::{
-            ChatCommandEvent, NpcStoreEvent, PersonalStoreEvent, PersonalStoreEventBuyItem,
-            PersonalStoreEventListItems, QuestTriggerEvent, UseItemEvent,
+            ChatCommandEvent, MailEvent, MailEventSend, MessengerEvent, NpcStoreEvent,
+            PersonalStoreEvent, PersonalStoreEventBuyItem, PersonalStoreEventListItems,
+            QuestTriggerEvent, UseItemEvent,
         },
         messages::{
             client::{
@@ -31,19 +34,63 @@ use crate::{
                 JoinZoneResponse, LogoutRequest, NpcStoreTransaction, PersonalStoreBuyItem,
                 QuestDelete, ReviveRequestType, SetHotbarSlot, SetHotbarSlotError,
             },
-            server::{self, LogoutReply, QuestDeleteResult, ServerMessage, UpdateBasicStat},
+            server::{
+                self, LogoutReply, QuestDeleteResult, ServerMessage, UpdateBasicStat,
+                UpdateXpStamina,
+            },
         },
         resources::{
-            ClientEntityList, GameData, LoginTokens, ServerMessages, ServerTime, WorldTime,
+            ClientEntityList, GameConfig, GameData, LoginTokens, ServerMessages, ServerTime,
+            WorldTime, ZoneList,
         },
     },
 };
 
+// Move any mail which was sent while the character was offline into their mailbox,
+// the character is saved before the pending mail is removed so it is never lost.
+fn receive_pending_mail(character: &mut CharacterStorage) {
+    let pending_mail = match MailStorage::try_load(&character.info.name) {
+        Ok(pending_mail) => pending_mail,
+        Err(error) => {
+            error!(
+                "Failed to load pending mail for character {} with error: {:?}",
+                character.info.name, error
+            );
+            return;
+        }
+    };
+    if pending_mail.mail.is_empty() {
+        return;
+    }
+
+    let num_mail = character.mailbox.mail.len();
+    character.mailbox.mail.extend(pending_mail.mail);
+
+    if let Err(error) = character.save() {
+        error!(
+            "Failed to save character {} with pending mail with error: {:?}",
+            character.info.name, error
+        );
+
+        // Leave the mail pending until the next login
+        character.mailbox.mail.truncate(num_mail);
+        return;
+    }
+
+    if let Err(error) = MailStorage::delete(&character.info.name) {
+        error!(
+            "Failed to delete pending mail for character {} with error: {:?}",
+            character.info.name, error
+        );
+    }
+}
+
 pub fn game_server_authentication_system(
     mut commands: Commands,
     mut query: Query<(Entity, &mut GameClient), Without<CharacterInfo>>,
     login_tokens: Res<LoginTokens>,
     game_data: Res<GameData>,
+    server_time: Res<ServerTime>,
 ) {
     query.for_each_mut(|(entity, mut game_client)| {
         if let Ok(message) = game_client.client_message_rx.try_recv() {
@@ -64,7 +111,9 @@ pub fn game_server_authentication_system(
                                         .ok()
                                         .ok_or(ConnectionRequestError::Failed)
                                 })
-                                .map(|character| {
+                                .map(|mut character| {
+                                    receive_pending_mail(&mut character);
+
                                     let status_effects = StatusEffects::new();
                                     let ability_values =
                                         game_data.ability_value_calculator.calculate(
@@ -122,13 +171,19 @@ pub fn game_server_authentication_system(
                                         ability_values,
                                         basic_stats: character.basic_stats.clone(),
                                         command: Command::default(),
+                                        cooldowns: Cooldowns::from_saved(
+                                            &character.cooldowns,
+                                            server_time.now,
+                                        ),
                                         equipment: character.equipment.clone(),
                                         experience_points: character.experience_points.clone(),
+                                        friend_list: character.friend_list.clone(),
                                         health_points,
                                         hotbar: character.hotbar.clone(),
                                         info: character.info.clone(),
                                         inventory: character.inventory.clone(),
                                         level: character.level.clone(),
+                                        mailbox: character.mailbox.clone(),
                                         mana_points,
                                         motion_data,
                                         move_mode,
@@ -191,7 +246,10 @@ pub fn game_server_join_system(
         Without<ClientEntity>,
     >,
     mut client_entity_list: ResMut<ClientEntityList>,
+    mut mail_events: EventWriter<MailEvent>,
+    mut messenger_events: EventWriter<MessengerEvent>,
     world_time: Res<WorldTime>,
+    zone_list: Res<ZoneList>,
 ) {
     query.for_each(
         |(
@@ -219,6 +277,9 @@ pub fn game_server_join_system(
                                 .insert(ClientEntityVisibility::new())
                                 .insert(PassiveRecoveryTime::default());
 
+                            mail_events.send(MailEvent::Deliver(entity));
+                            messenger_events.send(MessengerEvent::Login(entity));
+
                             message
                                 .response_tx
                                 .send(JoinZoneResponse {
@@ -229,6 +290,7 @@ pub fn game_server_join_system(
                                     health_points: *health_points,
                                     mana_points: *mana_points,
                                     world_ticks: world_time.ticks,
+                                    is_pvp_enabled: zone_list.get_pvp_enabled(position.zone_id),
                                 })
                                 .ok();
                         }
@@ -380,16 +442,27 @@ pub fn game_server_main_system(
         &CharacterInfo,
         &SkillList,
         &mut QuestState,
-        &mut MoveMode,
+        (
+            &mut MoveMode,
+            Option<&LastShoutTime>,
+            Option<&DeathXpPenalty>,
+            &Level,
+            &mut ExperiencePoints,
+            &Stamina,
+        ),
     )>,
+    character_query: Query<(&CharacterInfo, &GameClient)>,
     world_client_query: Query<&WorldClient>,
     mut client_entity_list: ResMut<ClientEntityList>,
     mut chat_command_events: EventWriter<ChatCommandEvent>,
+    mut mail_events: EventWriter<MailEvent>,
+    mut messenger_events: EventWriter<MessengerEvent>,
     mut npc_store_events: EventWriter<NpcStoreEvent>,
     mut personal_store_events: EventWriter<PersonalStoreEvent>,
     mut quest_trigger_events: EventWriter<QuestTriggerEvent>,
     mut use_item_events: EventWriter<UseItemEvent>,
     mut server_messages: ResMut<ServerMessages>,
+    game_config: Res<GameConfig>,
     game_data: Res<GameData>,
     server_time: Res<ServerTime>,
 ) {
@@ -409,7 +482,14 @@ pub fn game_server_main_system(
             character_info,
             skill_list,
             mut quest_state,
-            mut move_mode,
+            (
+                mut move_mode,
+                last_shout_time,
+                death_xp_penalty,
+                level,
+                mut experience_points,
+                stamina,
+            ),
         )| {
             let mut entity_commands = commands.entity(entity);
 
@@ -428,6 +508,57 @@ pub fn game_server_main_system(
                             );
                         }
                     }
+                    ClientMessage::Whisper(target_name, text) => {
+                        if let Some((_, target_client)) = character_query
+                            .iter()
+                            .find(|(target_info, _)| target_info.name == target_name)
+                        {
+                            target_client
+                                .server_message_tx
+                                .send(ServerMessage::Whisper(server::Whisper {
+                                    from: character_info.name.clone(),
+                                    text,
+                                }))
+                                .ok();
+                        } else {
+                            client
+                                .server_message_tx
+                                .send(ServerMessage::Whisper(server::Whisper {
+                                    from: String::from("SERVER"),
+                                    text: format!("{} is not online", target_name),
+                                }))
+                                .ok();
+                        }
+                    }
+                    ClientMessage::ShoutChat(text) => {
+                        let cooldown_remaining = last_shout_time.and_then(|last_shout_time| {
+                            (last_shout_time.when + SHOUT_COOLDOWN_DURATION)
+                                .checked_duration_since(server_time.now)
+                        });
+
+                        if let Some(cooldown_remaining) = cooldown_remaining {
+                            client
+                                .server_message_tx
+                                .send(ServerMessage::Whisper(server::Whisper {
+                                    from: String::from("SERVER"),
+                                    text: format!(
+                                        "You must wait {} seconds before shouting again",
+                                        cooldown_remaining.as_secs() + 1
+                                    ),
+                                }))
+                                .ok();
+                        } else {
+                            // Shout is sent to everyone in the adjacent sectors
+                            server_messages.send_entity_message(
+                                client_entity,
+                                ServerMessage::ShoutChat(server::ShoutChat {
+                                    name: character_info.name.clone(),
+                                    text,
+                                }),
+                            );
+                            entity_commands.insert(LastShoutTime::new(server_time.now));
+                        }
+                    }
                     ClientMessage::Move(message) => {
                         let mut move_target_entity = None;
                         if let Some(target_entity_id) = message.target_entity_id {
@@ -689,6 +820,29 @@ pub fn game_server_main_system(
                                 ),
                             };
 
+                            if death_xp_penalty.is_some() {
+                                // Reviving at the nearest revive point costs extra xp, save point is free
+                                if matches!(revive_request_type, ReviveRequestType::RevivePosition)
+                                {
+                                    experience_points_apply_penalty(
+                                        game_data.ability_value_calculator.as_ref(),
+                                        level,
+                                        &mut experience_points,
+                                        game_config.revive_position_xp_penalty_percent,
+                                    );
+                                    client
+                                        .server_message_tx
+                                        .send(ServerMessage::UpdateXpStamina(UpdateXpStamina {
+                                            xp: experience_points.xp,
+                                            stamina: stamina.stamina,
+                                            source_entity_id: None,
+                                        }))
+                                        .ok();
+                                }
+
+                                entity_commands.remove::<DeathXpPenalty>();
+                            }
+
                             entity_commands
                                 .insert(HealthPoints::new(ability_values.get_max_health() as u32))
                                 .insert(ManaPoints::new(ability_values.get_max_mana() as u32));
@@ -906,6 +1060,34 @@ pub fn game_server_main_system(
                             }),
                         );
                     }
+                    ClientMessage::MessengerAddFriend(name) => {
+                        messenger_events.send(MessengerEvent::AddFriendRequest(entity, name));
+                    }
+                    ClientMessage::MessengerAddFriendAccept(name) => {
+                        messenger_events.send(MessengerEvent::AddFriendAccept(entity, name));
+                    }
+                    ClientMessage::MessengerAddFriendReject(name) => {
+                        messenger_events.send(MessengerEvent::AddFriendReject(entity, name));
+                    }
+                    ClientMessage::MessengerDeleteFriend(tag) => {
+                        messenger_events.send(MessengerEvent::DeleteFriend(entity, tag));
+                    }
+                    ClientMessage::MessengerToggleBlockFriend(tag) => {
+                        messenger_events.send(MessengerEvent::ToggleBlockFriend(entity, tag));
+                    }
+                    ClientMessage::MessengerChat(tag, text) => {
+                        messenger_events.send(MessengerEvent::Chat(entity, tag, text));
+                    }
+                    ClientMessage::SendMail(recipient_name, text) => {
+                        mail_events.send(MailEvent::Send(MailEventSend {
+                            sender_entity: entity,
+                            recipient_name,
+                            text,
+                            item_slot: None,
+                            item_quantity: None,
+                            money: None,
+                        }));
+                    }
                     _ => warn!("Received unimplemented client message {:?}", message),
                 }
             }
@@ -0,0 +1,279 @@
+use bevy_ecs::prelude::{Entity, EventReader, EventWriter, Query};
+use log::error;
+
+use crate::{
+    data::{character::CharacterStorage, item::Item, mail::MailStorage},
+    game::{
+        components::{CharacterInfo, GameClient, Inventory, ItemSlot, Mail, Mailbox, Money},
+        events::{MailEvent, MailEventSend, SaveEvent},
+        messages::server::{ServerMessage, Whisper},
+    },
+};
+
+enum MailSendError {
+    InvalidRecipient,
+    InvalidItem,
+    NotEnoughMoney,
+}
+
+fn send_server_whisper(game_client: &GameClient, text: String) {
+    game_client
+        .server_message_tx
+        .send(ServerMessage::Whisper(Whisper {
+            from: String::from("SERVER"),
+            text,
+        }))
+        .ok();
+}
+
+fn take_mail_attachments(
+    inventory: &mut Inventory,
+    item_slot: Option<ItemSlot>,
+    item_quantity: Option<u32>,
+    money: Option<Money>,
+) -> Result<Option<Item>, MailSendError> {
+    if let Some(money) = money {
+        if money <= Money(0) || inventory.money < money {
+            return Err(MailSendError::NotEnoughMoney);
+        }
+    }
+
+    if item_quantity == Some(0) {
+        return Err(MailSendError::InvalidItem);
+    }
+
+    let item = if let Some(item_slot) = item_slot {
+        let item = inventory
+            .get_item(item_slot)
+            .ok_or(MailSendError::InvalidItem)?;
+        if item.get_item_type().is_quest_item() {
+            return Err(MailSendError::InvalidItem);
+        }
+
+        let quantity = item_quantity.unwrap_or_else(|| item.get_quantity());
+        Some(
+            inventory
+                .try_take_quantity(item_slot, quantity)
+                .ok_or(MailSendError::InvalidItem)?,
+        )
+    } else {
+        None
+    };
+
+    if let Some(money) = money {
+        inventory.try_take_money(money).ok();
+    }
+
+    Ok(item)
+}
+
+fn deliver_mail(game_client: &GameClient, inventory: &mut Inventory, mailbox: &mut Mailbox) {
+    let mut updated_slots = Vec::new();
+    let mut updated_money = false;
+
+    for mail in mailbox.mail.iter_mut() {
+        if !mail.is_read {
+            send_server_whisper(
+                game_client,
+                format!("Mail from {}: {}", mail.from, mail.text),
+            );
+            mail.is_read = true;
+        }
+
+        if let Some(money) = mail.money.take() {
+            match inventory.try_add_money(money) {
+                Ok(_) => updated_money = true,
+                Err(money) => mail.money = Some(money),
+            }
+        }
+
+        if let Some(item) = mail.item.take() {
+            match inventory.try_add_item(item) {
+                Ok((slot, item)) => updated_slots.push((slot, Some(item.clone()))),
+                Err(item) => mail.item = Some(item),
+            }
+        }
+    }
+
+    // Mail is kept until all of its attachments have been delivered
+    mailbox
+        .mail
+        .retain(|mail| mail.item.is_some() || mail.money.is_some());
+
+    if !updated_slots.is_empty() || updated_money {
+        game_client
+            .server_message_tx
+            .send(ServerMessage::UpdateInventory(
+                updated_slots,
+                if updated_money {
+                    Some(inventory.money)
+                } else {
+                    None
+                },
+            ))
+            .ok();
+    }
+
+    if !mailbox.mail.is_empty() {
+        send_server_whisper(
+            game_client,
+            format!(
+                "Your inventory is full, {} mail attachment(s) are waiting for you",
+                mailbox.mail.len()
+            ),
+        );
+    }
+}
+
+pub fn mail_system(
+    character_query: Query<(Entity, &CharacterInfo)>,
+    mut mail_query: Query<(&GameClient, &mut Inventory, &mut Mailbox)>,
+    mut mail_events: EventReader<MailEvent>,
+    mut save_events: EventWriter<SaveEvent>,
+) {
+    for event in mail_events.iter() {
+        match *event {
+            MailEvent::Send(MailEventSend {
+                sender_entity,
+                ref recipient_name,
+                ref text,
+                item_slot,
+                item_quantity,
+                money,
+            }) => {
+                let sender_name = if let Ok((_, sender_info)) = character_query.get(sender_entity) {
+                    sender_info.name.clone()
+                } else {
+                    continue;
+                };
+
+                let recipient_entity = character_query
+                    .iter()
+                    .find(|(_, character_info)| &character_info.name == recipient_name)
+                    .map(|(entity, _)| entity);
+
+                let mail = if let Ok((sender_client, mut sender_inventory, _)) =
+                    mail_query.get_mut(sender_entity)
+                {
+                    let result = if &sender_name == recipient_name
+                        || (recipient_entity.is_none() && !CharacterStorage::exists(recipient_name))
+                    {
+                        Err(MailSendError::InvalidRecipient)
+                    } else {
+                        take_mail_attachments(
+                            &mut sender_inventory,
+                            item_slot,
+                            item_quantity,
+                            money,
+                        )
+                    };
+
+                    match result {
+                        Ok(item) => {
+                            if item_slot.is_some() || money.is_some() {
+                                sender_client
+                                    .server_message_tx
+                                    .send(ServerMessage::UpdateInventory(
+                                        item_slot
+                                            .map(|slot| {
+                                                vec![(
+                                                    slot,
+                                                    sender_inventory.get_item(slot).cloned(),
+                                                )]
+                                            })
+                                            .unwrap_or_default(),
+                                        money.map(|_| sender_inventory.money),
+                                    ))
+                                    .ok();
+                            }
+
+                            Mail::new(sender_name, text.clone(), item, money)
+                        }
+                        Err(MailSendError::InvalidRecipient) => {
+                            send_server_whisper(
+                                sender_client,
+                                format!("Character {} does not exist", recipient_name),
+                            );
+                            continue;
+                        }
+                        Err(MailSendError::InvalidItem) => {
+                            send_server_whisper(
+                                sender_client,
+                                String::from("Invalid mail item attachment"),
+                            );
+                            continue;
+                        }
+                        Err(MailSendError::NotEnoughMoney) => {
+                            send_server_whisper(
+                                sender_client,
+                                String::from("Not enough money for mail attachment"),
+                            );
+                            continue;
+                        }
+                    }
+                } else {
+                    continue;
+                };
+
+                let result = if let Some((
+                    recipient_client,
+                    mut recipient_inventory,
+                    mut recipient_mailbox,
+                )) =
+                    recipient_entity.and_then(|entity| mail_query.get_mut(entity).ok())
+                {
+                    recipient_mailbox.mail.push(mail);
+                    deliver_mail(
+                        recipient_client,
+                        &mut recipient_inventory,
+                        &mut recipient_mailbox,
+                    );
+                    Ok(())
+                } else {
+                    // Recipient is offline, queue the mail until they next log in
+                    MailStorage::try_load(recipient_name)
+                        .and_then(|mut pending_mail| {
+                            pending_mail.mail.push(mail.clone());
+                            pending_mail.save(recipient_name)
+                        })
+                        .map(|_| {
+                            // The mail has been persisted so we must also persist the
+                            // removal of its attachments from the sender
+                            save_events.send(SaveEvent::with_character(sender_entity, false));
+                        })
+                        .map_err(|error| (error, mail))
+                };
+
+                if let Ok((sender_client, mut sender_inventory, mut sender_mailbox)) =
+                    mail_query.get_mut(sender_entity)
+                {
+                    match result {
+                        Ok(_) => {
+                            send_server_whisper(
+                                sender_client,
+                                format!("Mail sent to {}", recipient_name),
+                            );
+                        }
+                        Err((error, mut mail)) => {
+                            error!(
+                                "Failed to send mail to character {} with error: {:?}",
+                                recipient_name, error
+                            );
+
+                            // Return the mail and its attachments to the sender
+                            mail.from = String::from("SERVER");
+                            mail.text = format!("Failed to send mail to {}", recipient_name);
+                            sender_mailbox.mail.push(mail);
+                            deliver_mail(sender_client, &mut sender_inventory, &mut sender_mailbox);
+                        }
+                    }
+                }
+            }
+            MailEvent::Deliver(entity) => {
+                if let Ok((game_client, mut inventory, mut mailbox)) = mail_query.get_mut(entity) {
+                    deliver_mail(game_client, &mut inventory, &mut mailbox);
+                }
+            }
+        }
+    }
+}
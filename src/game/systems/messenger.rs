@@ -0,0 +1,439 @@
+use bevy_ecs::prelude::{Entity, EventReader, Mut, Query};
+
+use crate::game::{
+    components::{CharacterInfo, ClientEntity, ClientEntityId, FriendList, GameClient},
+    events::MessengerEvent,
+    messages::server::{MessengerChat, MessengerFriend, MessengerFriendStatus, ServerMessage},
+};
+
+type CharacterQuery<'a> = Query<
+    'a,
+    (
+        Entity,
+        &'a CharacterInfo,
+        Option<&'a ClientEntity>,
+        &'a GameClient,
+    ),
+>;
+
+fn find_online_character<'a>(
+    character_query: &'a CharacterQuery,
+    name: &str,
+) -> Option<(Entity, &'a GameClient)> {
+    character_query
+        .iter()
+        .find(|(_, character_info, _, _)| character_info.name == name)
+        .map(|(entity, _, _, game_client)| (entity, game_client))
+}
+
+fn send_message(character_query: &CharacterQuery, entity: Entity, message: ServerMessage) {
+    if let Ok((_, _, _, game_client)) = character_query.get(entity) {
+        game_client.server_message_tx.send(message).ok();
+    }
+}
+
+// Send the status of name to friend_name, if they are online and have name on their friend list
+fn send_status_to_friend(
+    character_query: &CharacterQuery,
+    friend_list_query: &mut Query<&mut FriendList>,
+    name: &str,
+    friend_name: &str,
+    status: MessengerFriendStatus,
+) {
+    if let Some((friend_entity, friend_client)) =
+        find_online_character(character_query, friend_name)
+    {
+        if let Some(tag) = friend_list_query
+            .get_mut(friend_entity)
+            .ok()
+            .and_then(|friend_list| friend_list.find_by_name(name).map(|friend| friend.tag))
+        {
+            friend_client
+                .server_message_tx
+                .send(ServerMessage::MessengerFriendStatus(tag, status))
+                .ok();
+        }
+    }
+}
+
+// Get the status of friend_name as seen by name
+fn get_friend_status(
+    character_query: &CharacterQuery,
+    friend_list_query: &mut Query<&mut FriendList>,
+    name: &str,
+    friend_name: &str,
+) -> MessengerFriendStatus {
+    find_online_character(character_query, friend_name)
+        .and_then(|(friend_entity, _)| friend_list_query.get_mut(friend_entity).ok())
+        .map_or(MessengerFriendStatus::Offline, |friend_list| {
+            match friend_list.find_by_name(name) {
+                // They have removed us from their friend list
+                None => MessengerFriendStatus::Deleted,
+                Some(friend) if friend.blocked => MessengerFriendStatus::Offline,
+                Some(_) => MessengerFriendStatus::Online,
+            }
+        })
+}
+
+fn get_character_name(character_query: &CharacterQuery, entity: Entity) -> Option<String> {
+    character_query
+        .get(entity)
+        .ok()
+        .map(|(_, character_info, _, _)| character_info.name.clone())
+}
+
+fn add_friend(friend_list: &mut Mut<FriendList>, name: &str) -> Option<u32> {
+    if let Some(friend) = friend_list.find_by_name(name) {
+        return Some(friend.tag);
+    }
+
+    friend_list
+        .try_add_friend(name.to_string())
+        .map(|friend| friend.tag)
+}
+
+pub fn messenger_system(
+    character_query: CharacterQuery,
+    mut friend_list_query: Query<&mut FriendList>,
+    mut messenger_events: EventReader<MessengerEvent>,
+) {
+    for event in messenger_events.iter() {
+        match *event {
+            MessengerEvent::Login(entity) => {
+                let name = if let Some(name) = get_character_name(&character_query, entity) {
+                    name
+                } else {
+                    continue;
+                };
+
+                // Only notify our friends the first time we join a zone
+                let friends = if let Ok(mut friend_list) = friend_list_query.get_mut(entity) {
+                    if friend_list.is_online {
+                        continue;
+                    }
+                    friend_list.is_online = true;
+                    friend_list.friends.clone()
+                } else {
+                    continue;
+                };
+
+                let mut messenger_friends = Vec::with_capacity(friends.len());
+                for friend in friends {
+                    let status = if friend.blocked {
+                        MessengerFriendStatus::Blocked
+                    } else {
+                        send_status_to_friend(
+                            &character_query,
+                            &mut friend_list_query,
+                            &name,
+                            &friend.name,
+                            MessengerFriendStatus::Online,
+                        );
+                        get_friend_status(
+                            &character_query,
+                            &mut friend_list_query,
+                            &name,
+                            &friend.name,
+                        )
+                    };
+
+                    messenger_friends.push(MessengerFriend {
+                        tag: friend.tag,
+                        name: friend.name,
+                        status,
+                    });
+                }
+
+                send_message(
+                    &character_query,
+                    entity,
+                    ServerMessage::MessengerFriendList(messenger_friends),
+                );
+            }
+            MessengerEvent::Logout(entity) => {
+                let name = if let Some(name) = get_character_name(&character_query, entity) {
+                    name
+                } else {
+                    continue;
+                };
+
+                let friends = if let Ok(friend_list) = friend_list_query.get_mut(entity) {
+                    friend_list.friends.clone()
+                } else {
+                    continue;
+                };
+
+                for friend in friends.iter().filter(|friend| !friend.blocked) {
+                    send_status_to_friend(
+                        &character_query,
+                        &mut friend_list_query,
+                        &name,
+                        &friend.name,
+                        MessengerFriendStatus::Offline,
+                    );
+                }
+            }
+            MessengerEvent::AddFriendRequest(entity, ref friend_name) => {
+                let (name, entity_id) = if let Ok((_, character_info, client_entity, _)) =
+                    character_query.get(entity)
+                {
+                    (
+                        character_info.name.clone(),
+                        client_entity.map_or(ClientEntityId(0), |client_entity| client_entity.id),
+                    )
+                } else {
+                    continue;
+                };
+
+                if friend_list_query
+                    .get_mut(entity)
+                    .map_or(true, |friend_list| {
+                        friend_list.is_full() || friend_list.find_by_name(friend_name).is_some()
+                    })
+                {
+                    continue;
+                }
+
+                let friend = find_online_character(&character_query, friend_name)
+                    .filter(|(friend_entity, _)| *friend_entity != entity);
+                let mut friend_friend_list = friend.and_then(|(friend_entity, _)| {
+                    friend_list_query
+                        .get_mut(friend_entity)
+                        .ok()
+                        .filter(|friend_list| !friend_list.is_blocked(&name))
+                });
+
+                match (friend, friend_friend_list.as_mut()) {
+                    (Some((_, friend_client)), Some(friend_friend_list)) => {
+                        if !friend_friend_list.pending_requests.contains(&name) {
+                            friend_friend_list.pending_requests.push(name.clone());
+                        }
+
+                        friend_client
+                            .server_message_tx
+                            .send(ServerMessage::MessengerAddFriendRequest(entity_id, name))
+                            .ok();
+                    }
+                    _ => {
+                        send_message(
+                            &character_query,
+                            entity,
+                            ServerMessage::MessengerAddFriendNotFound(friend_name.clone()),
+                        );
+                    }
+                }
+            }
+            MessengerEvent::AddFriendAccept(entity, ref requester_name) => {
+                let name = if let Some(name) = get_character_name(&character_query, entity) {
+                    name
+                } else {
+                    continue;
+                };
+
+                // Only accept if the requester actually sent us a request
+                if let Ok(mut friend_list) = friend_list_query.get_mut(entity) {
+                    if let Some(index) = friend_list
+                        .pending_requests
+                        .iter()
+                        .position(|pending_name| pending_name == requester_name)
+                    {
+                        friend_list.pending_requests.remove(index);
+                    } else {
+                        continue;
+                    }
+                } else {
+                    continue;
+                }
+
+                let requester_entity = if let Some((requester_entity, _)) =
+                    find_online_character(&character_query, requester_name)
+                {
+                    requester_entity
+                } else {
+                    send_message(
+                        &character_query,
+                        entity,
+                        ServerMessage::MessengerAddFriendNotFound(requester_name.clone()),
+                    );
+                    continue;
+                };
+
+                // Both friend lists must have space, otherwise treat it as a rejection
+                let is_friend_list_full = [(entity, requester_name), (requester_entity, &name)]
+                    .iter()
+                    .any(|&(add_entity, add_name)| {
+                        friend_list_query
+                            .get_mut(add_entity)
+                            .map_or(true, |friend_list| {
+                                friend_list.is_full()
+                                    && friend_list.find_by_name(add_name).is_none()
+                            })
+                    });
+                if is_friend_list_full {
+                    send_message(
+                        &character_query,
+                        requester_entity,
+                        ServerMessage::MessengerAddFriendRejected(name),
+                    );
+                    continue;
+                }
+
+                for &(add_entity, add_name) in
+                    [(entity, requester_name), (requester_entity, &name)].iter()
+                {
+                    if let Ok(mut friend_list) = friend_list_query.get_mut(add_entity) {
+                        if let Some(tag) = add_friend(&mut friend_list, add_name) {
+                            send_message(
+                                &character_query,
+                                add_entity,
+                                ServerMessage::MessengerAddFriendAccepted(MessengerFriend {
+                                    tag,
+                                    name: add_name.clone(),
+                                    status: MessengerFriendStatus::Online,
+                                }),
+                            );
+                        }
+                    }
+                }
+            }
+            MessengerEvent::AddFriendReject(entity, ref requester_name) => {
+                let name = if let Some(name) = get_character_name(&character_query, entity) {
+                    name
+                } else {
+                    continue;
+                };
+
+                if let Ok(mut friend_list) = friend_list_query.get_mut(entity) {
+                    friend_list
+                        .pending_requests
+                        .retain(|pending_name| pending_name != requester_name);
+                }
+
+                if let Some((_, requester_client)) =
+                    find_online_character(&character_query, requester_name)
+                {
+                    requester_client
+                        .server_message_tx
+                        .send(ServerMessage::MessengerAddFriendRejected(name))
+                        .ok();
+                }
+            }
+            MessengerEvent::DeleteFriend(entity, tag) => {
+                let name = if let Some(name) = get_character_name(&character_query, entity) {
+                    name
+                } else {
+                    continue;
+                };
+
+                let friend = if let Ok(mut friend_list) = friend_list_query.get_mut(entity) {
+                    friend_list.remove_friend(tag)
+                } else {
+                    continue;
+                };
+
+                // We stay on their friend list, but they will see that we have deleted them
+                if let Some(friend) = friend.filter(|friend| !friend.blocked) {
+                    send_status_to_friend(
+                        &character_query,
+                        &mut friend_list_query,
+                        &name,
+                        &friend.name,
+                        MessengerFriendStatus::Deleted,
+                    );
+                }
+            }
+            MessengerEvent::ToggleBlockFriend(entity, tag) => {
+                let name = if let Some(name) = get_character_name(&character_query, entity) {
+                    name
+                } else {
+                    continue;
+                };
+
+                let (friend_name, blocked) =
+                    if let Ok(mut friend_list) = friend_list_query.get_mut(entity) {
+                        if let Some(friend) = friend_list.find_by_tag_mut(tag) {
+                            friend.blocked = !friend.blocked;
+                            (friend.name.clone(), friend.blocked)
+                        } else {
+                            continue;
+                        }
+                    } else {
+                        continue;
+                    };
+
+                let status = if blocked {
+                    MessengerFriendStatus::Blocked
+                } else {
+                    get_friend_status(
+                        &character_query,
+                        &mut friend_list_query,
+                        &name,
+                        &friend_name,
+                    )
+                };
+                send_message(
+                    &character_query,
+                    entity,
+                    ServerMessage::MessengerFriendStatus(tag, status),
+                );
+
+                // A blocked friend will see us as offline
+                send_status_to_friend(
+                    &character_query,
+                    &mut friend_list_query,
+                    &name,
+                    &friend_name,
+                    if blocked {
+                        MessengerFriendStatus::Offline
+                    } else {
+                        MessengerFriendStatus::Online
+                    },
+                );
+            }
+            MessengerEvent::Chat(entity, tag, ref text) => {
+                let name = if let Some(name) = get_character_name(&character_query, entity) {
+                    name
+                } else {
+                    continue;
+                };
+
+                let friend_name = if let Some(friend_name) = friend_list_query
+                    .get_mut(entity)
+                    .ok()
+                    .and_then(|friend_list| {
+                        friend_list
+                            .find_by_tag(tag)
+                            .filter(|friend| !friend.blocked)
+                            .map(|friend| friend.name.clone())
+                    }) {
+                    friend_name
+                } else {
+                    continue;
+                };
+
+                if let Some((friend_entity, friend_client)) =
+                    find_online_character(&character_query, &friend_name)
+                {
+                    if let Some(friend_tag) = friend_list_query
+                        .get_mut(friend_entity)
+                        .ok()
+                        .and_then(|friend_list| {
+                            friend_list
+                                .find_by_name(&name)
+                                .filter(|friend| !friend.blocked)
+                                .map(|friend| friend.tag)
+                        })
+                    {
+                        friend_client
+                            .server_message_tx
+                            .send(ServerMessage::MessengerChat(MessengerChat {
+                                tag: friend_tag,
+                                text: text.clone(),
+                            }))
+                            .ok();
+                    }
+                }
+            }
+        }
+    }
+}
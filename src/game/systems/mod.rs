@@ -9,6 +9,8 @@ mod experience_points;
 mod expire_time;
 mod game_server;
 mod login_server;
+mod mail;
+mod messenger;
 mod monster_spawn;
 mod npc_ai;
 mod npc_store_system;
@@ -39,12 +41,14 @@ pub use game_server::{
     game_server_authentication_system, game_server_join_system, game_server_main_system,
 };
 pub use login_server::{login_server_authentication_system, login_server_system};
+pub use mail::mail_system;
+pub use messenger::messenger_system;
 pub use monster_spawn::monster_spawn_system;
 pub use npc_ai::npc_ai_system;
 pub use npc_store_system::npc_store_system;
 pub use passive_recovery_system::passive_recovery_system;
 pub use personal_store::personal_store_system;
-pub use quest::quest_system;
+pub use quest::{is_quest_condition_implemented, is_quest_reward_implemented, quest_system};
 pub use save::save_system;
 pub use server_messages::server_messages_system;
 pub use skill_effect::skill_effect_system;
@@ -6,31 +6,38 @@ use std::{
 
 use bevy_ecs::prelude::{Commands, Entity, EventWriter, Query, Res, ResMut};
 use chrono::{Datelike, Timelike};
-use log::{trace, warn};
-use nalgebra::{Point3, Vector3};
+use log::{info, trace, warn};
+use nalgebra::{Point3, Vector2, Vector3};
 use rand::{prelude::ThreadRng, Rng};
 
 use crate::{
     data::{
         formats::{
-            AipAbilityType, AipAction, AipCondition, AipConditionFindNearbyEntities,
-            AipConditionMonthDayTime, AipConditionWeekDayTime, AipDamageType, AipDistanceOrigin,
-            AipEvent, AipHaveStatusTarget, AipHaveStatusType, AipMoveMode, AipMoveOrigin, AipNpcId,
-            AipOperatorType, AipTrigger, AipVariableType,
+            AipAbilityType, AipAction, AipAttackNearbyStat, AipCondition,
+            AipConditionFindNearbyEntities, AipConditionMonthDayTime, AipConditionWeekDayTime,
+            AipDamageType, AipDistanceOrigin, AipEvent, AipHaveStatusTarget, AipHaveStatusType,
+            AipIsSpawnOwner, AipMessageType, AipMonsterSpawnState, AipMotionId, AipMoveMode,
+            AipMoveOrigin, AipNpcId, AipOperatorType, AipResultOperator, AipSkillId,
+            AipSkillTarget, AipSpawnNpcOrigin, AipTrigger, AipVariableType, AipZoneId,
         },
-        Damage, NpcId,
+        item::Item,
+        Damage, ItemReference, MotionId, NpcId, SkillId, SkillType, ZoneId,
     },
     game::{
-        bundles::client_entity_leave_zone,
+        bundles::{client_entity_leave_zone, DroppedItemBundle, MonsterBundle},
         components::{
-            AbilityValues, ClientEntity, ClientEntityType, Command, CommandData, CommandDie,
-            DamageSources, SpawnExpireTime, GameClient, HealthPoints, Level, MonsterSpawnPoint,
-            MoveMode, NextCommand, Npc, NpcAi, ObjectVariables, Owner, Position, SpawnOrigin,
-            StatusEffects, Target, Team, OwnedExpireTime,
+            AbilityValues, ClientEntity, ClientEntityType, Command, CommandCastSkillTarget,
+            CommandData, CommandDie, DamageSources, DroppedItem, GameClient, HealthPoints,
+            Inventory, Level, MonsterSpawnPoint, MoveMode, MoveSpeed, NextCommand, Npc, NpcAi,
+            ObjectVariables, OwnedExpireTime, Owner, Position, QuestState, SpawnExpireTime,
+            SpawnOrigin, StatusEffects, Target, Team,
+        },
+        events::{DamageEvent, QuestTriggerEvent, RewardXpEvent},
+        messages::server::{AnnounceChat, LocalChat, ServerMessage, ShoutChat, UseEmote},
+        resources::{
+            ClientEntityList, ServerMessages, ServerTime, WorldRates, WorldTime, WorldVariables,
+            ZoneList,
         },
-        events::RewardXpEvent,
-        messages::server::ServerMessage,
-        resources::{ClientEntityList, ServerTime, WorldRates, WorldTime, ZoneList},
         GameData,
     },
 };
@@ -40,13 +47,17 @@ const DROPPED_ITEM_SPAWN_EXPIRE_TIME: Duration = Duration::from_secs(2 * 60);
 const DROPPED_ITEM_OWNED_EXPIRE_TIME: Duration = Duration::from_secs(60);
 const DROP_ITEM_RADIUS: i32 = 200;
 
-struct AiWorld<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k, 'l, 'm, 'n> {
+struct AiWorld<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k, 'l, 'm, 'n, 'o, 'p, 'q, 'r, 's, 't> {
     commands: &'a mut Commands<'b>,
     client_entity_list: &'a mut ClientEntityList,
+    server_messages: &'a mut ServerMessages,
+    world_variables: &'a mut WorldVariables,
+    zone_list: &'a mut ZoneList,
+    quest_trigger_events: &'a mut EventWriter<'p, QuestTriggerEvent>,
+    damage_events: &'a mut EventWriter<'t, DamageEvent>,
     game_data: &'c GameData,
     server_time: &'c ServerTime,
     world_time: &'c WorldTime,
-    zone_list: &'c ZoneList,
     target_query: Query<
         'd,
         (
@@ -55,29 +66,34 @@ struct AiWorld<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k, 'l, 'm, 'n> {
             &'g AbilityValues,
             &'h StatusEffects,
             &'i HealthPoints,
+            Option<&'o Npc>,
         ),
     >,
     object_variable_query: Query<'j, &'k mut ObjectVariables>,
     owner_query: Query<'l, (&'m Position, Option<&'n Target>)>,
+    inventory_query: Query<'q, (&'r GameClient, &'s mut Inventory, &'s mut QuestState)>,
     rng: ThreadRng,
 }
 
 struct AiSourceData<'a> {
     entity: Entity,
     ability_values: &'a AbilityValues,
+    client_entity: &'a ClientEntity,
     health_points: &'a HealthPoints,
     level: &'a Level,
+    npc: &'a Npc,
     owner: Option<Entity>,
     position: &'a Position,
     spawn_origin: Option<&'a SpawnOrigin>,
     status_effects: &'a StatusEffects,
     target: Option<Entity>,
     team: &'a Team,
+    is_trace_enabled: bool,
 }
 
 struct AiAttackerData<'a> {
     entity: Entity,
-    _position: &'a Position,
+    position: &'a Position,
     _team: &'a Team,
     ability_values: &'a AbilityValues,
     health_points: &'a HealthPoints,
@@ -138,16 +154,18 @@ fn ai_condition_count_nearby_entities(
         }
 
         // Check level and team requirements
-        let meets_requirements =
-            ai_world
-                .target_query
-                .get(entity)
-                .map_or(false, |(level, team, ..)| {
-                    let level_diff = ai_parameters.source.level.level as i32 - level.level as i32;
-
-                    is_allied == (team.id == ai_parameters.source.team.id)
-                        && level_diff_range.contains(&level_diff)
-                });
+        let meets_requirements = ai_world.target_query.get(entity).map_or(
+            false,
+            |(level, team, _, status_effects, ..)| {
+                let level_diff = ai_parameters.source.level.level as i32 - level.level as i32;
+                let is_team_allied = team.id == ai_parameters.source.team.id;
+
+                // We can not find invisible entities which are not our allies
+                is_allied == is_team_allied
+                    && (is_team_allied || !status_effects.is_invisible())
+                    && level_diff_range.contains(&level_diff)
+            },
+        );
         if !meets_requirements {
             continue;
         }
@@ -427,20 +445,18 @@ fn ai_condition_variable(
             .ok()
             .and_then(|object_variables| object_variables.variables.get(variable_id).copied())
             .unwrap_or(0),
-        AipVariableType::World => {
-            warn!(
-                "Unimplemented ai_condition_variable with variable type {:?}",
-                variable_type
-            );
-            0
-        }
-        AipVariableType::Economy => {
-            warn!(
-                "Unimplemented ai_condition_variable with variable type {:?}",
-                variable_type
-            );
-            0
-        }
+        AipVariableType::World => ai_world
+            .world_variables
+            .world
+            .get(variable_id)
+            .copied()
+            .unwrap_or(0),
+        AipVariableType::Economy => ai_world
+            .world_variables
+            .economy
+            .get(variable_id)
+            .copied()
+            .unwrap_or(0),
     };
 
     compare_aip_value(operator_type, variable_value, value)
@@ -467,7 +483,7 @@ fn ai_condition_has_status_effect(
             .source
             .target
             .and_then(|target_entity| ai_world.target_query.get(target_entity).ok())
-            .map(|(_, _, _, status_effects, _)| status_effects),
+            .map(|(_, _, _, status_effects, ..)| status_effects),
     };
 
     if let Some(status_effects) = status_effects {
@@ -521,7 +537,7 @@ fn ai_condition_target_ability_value(
     aip_ability_type: AipAbilityType,
     value: i32,
 ) -> bool {
-    if let Some((_, _, ability_values, _, health_points)) = ai_parameters
+    if let Some((_, _, ability_values, _, health_points, _)) = ai_parameters
         .source
         .target
         .and_then(|target_entity| ai_world.target_query.get(target_entity).ok())
@@ -551,7 +567,7 @@ fn ai_condition_attacker_and_target_ability_value(
         .source
         .target
         .and_then(|target_entity| ai_world.target_query.get(target_entity).ok())
-        .map(|(_, _, ability_values, _, health_points)| {
+        .map(|(_, _, ability_values, _, health_points, _)| {
             get_aip_ability_value(ability_values, health_points, aip_ability_type)
         });
 
@@ -691,6 +707,12 @@ fn npc_ai_check_conditions(
         };
 
         if !result {
+            if ai_parameters.source.is_trace_enabled {
+                info!(
+                    "AI trace {:?}: condition failed {:?}",
+                    ai_parameters.source.entity, condition
+                );
+            }
             return false;
         }
     }
@@ -769,14 +791,586 @@ fn ai_action_move_near_owner(ai_world: &mut AiWorld, ai_parameters: &mut AiParam
     }
 }
 
+fn ai_action_spawn_npc(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    npc_id: AipNpcId,
+    distance: i32,
+    spawn_origin: AipSpawnNpcOrigin,
+    is_spawn_owner: AipIsSpawnOwner,
+) {
+    let npc_id = if let Some(npc_id) = NpcId::new(npc_id as u16) {
+        npc_id
+    } else {
+        return;
+    };
+
+    let spawn_position = match spawn_origin {
+        AipSpawnNpcOrigin::CurrentPosition => Some(ai_parameters.source.position.position),
+        AipSpawnNpcOrigin::AttackerPosition => ai_parameters
+            .attacker
+            .map(|attacker| attacker.position.position),
+        AipSpawnNpcOrigin::TargetPosition => ai_parameters
+            .source
+            .target
+            .and_then(|target_entity| ai_world.owner_query.get(target_entity).ok())
+            .map(|(position, _)| position.position),
+    };
+
+    if let Some(spawn_position) = spawn_position {
+        let owner = if is_spawn_owner {
+            Some((ai_parameters.source.entity, ai_parameters.source.level))
+        } else {
+            None
+        };
+
+        MonsterBundle::spawn(
+            ai_world.commands,
+            ai_world.client_entity_list,
+            ai_world.game_data,
+            npc_id,
+            ai_parameters.source.position.zone_id,
+            SpawnOrigin::Summoned(ai_parameters.source.entity, spawn_position),
+            distance,
+            ai_parameters.source.team.clone(),
+            owner,
+            None,
+        );
+    }
+}
+
+fn ai_action_transform_npc(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    npc_id: AipNpcId,
+) {
+    if ai_parameters.is_dead {
+        return;
+    }
+
+    let npc_id = if let Some(npc_id) = NpcId::new(npc_id as u16) {
+        npc_id
+    } else {
+        return;
+    };
+
+    let npc_data = if let Some(npc_data) = ai_world.game_data.npcs.get_npc(npc_id) {
+        npc_data
+    } else {
+        return;
+    };
+
+    let ability_values = if let Some(ability_values) = ai_world
+        .game_data
+        .ability_value_calculator
+        .calculate_npc(npc_id, ai_parameters.source.status_effects, None, None)
+    {
+        ability_values
+    } else {
+        return;
+    };
+
+    let health_points = HealthPoints::new(u32::min(
+        ai_parameters.source.health_points.hp,
+        ability_values.get_max_health() as u32,
+    ));
+    let level = Level::new(ability_values.get_level() as u32);
+    let move_speed = MoveSpeed::new(ability_values.get_walk_speed() as f32);
+
+    let mut entity_commands = ai_world.commands.entity(ai_parameters.source.entity);
+    entity_commands
+        .insert(Npc::new(npc_id, ai_parameters.source.npc.quest_index))
+        .insert(ai_world.game_data.npcs.get_npc_action_motions(npc_id))
+        .insert(health_points)
+        .insert(level)
+        .insert(MoveMode::Walk)
+        .insert(move_speed)
+        .insert(ability_values);
+
+    if npc_data.ai_file_index != 0 {
+        entity_commands.insert(NpcAi::new(npc_data.ai_file_index as usize));
+    } else {
+        entity_commands.remove::<NpcAi>();
+    }
+
+    ai_world.server_messages.send_entity_message(
+        ai_parameters.source.client_entity,
+        ServerMessage::ChangeNpcId(ai_parameters.source.client_entity.id, npc_id),
+    );
+}
+
+fn ai_action_use_skill(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    skill_target: AipSkillTarget,
+    skill_id: AipSkillId,
+    motion_id: AipMotionId,
+) {
+    let skill_data = if let Some(skill_data) = SkillId::new(skill_id as u16)
+        .and_then(|skill_id| ai_world.game_data.skills.get_skill(skill_id))
+    {
+        skill_data
+    } else {
+        return;
+    };
+
+    let target = match skill_target {
+        AipSkillTarget::FindChar => ai_parameters.find_char,
+        AipSkillTarget::NearChar => ai_parameters.near_char,
+        AipSkillTarget::Target => ai_parameters.source.target.and_then(|target_entity| {
+            ai_world
+                .owner_query
+                .get(target_entity)
+                .ok()
+                .map(|(position, _)| (target_entity, position.position))
+        }),
+        AipSkillTarget::This => Some((
+            ai_parameters.source.entity,
+            ai_parameters.source.position.position,
+        )),
+    };
+
+    let command_skill_target = if skill_data.skill_type.is_self_skill() {
+        None
+    } else if let Some((target_entity, target_position)) = target {
+        if matches!(skill_data.skill_type, SkillType::AreaTarget) {
+            Some(CommandCastSkillTarget::Position(target_position.xy()))
+        } else {
+            Some(CommandCastSkillTarget::Entity(target_entity))
+        }
+    } else {
+        return;
+    };
+
+    ai_world
+        .commands
+        .entity(ai_parameters.source.entity)
+        .insert(NextCommand::with_npc_cast_skill(
+            skill_data.id,
+            command_skill_target,
+            motion_id as usize,
+        ));
+}
+
+fn ai_action_nearby_allies_attack_target(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    count: usize,
+    distance: i32,
+    npc_id: Option<AipNpcId>,
+) {
+    let target_entity = if let Some(target_entity) = ai_parameters
+        .source
+        .target
+        .or_else(|| ai_parameters.attacker.map(|attacker| attacker.entity))
+    {
+        target_entity
+    } else {
+        return;
+    };
+
+    let zone_entities = if let Some(zone_entities) = ai_world
+        .client_entity_list
+        .get_zone(ai_parameters.source.position.zone_id)
+    {
+        zone_entities
+    } else {
+        return;
+    };
+
+    let mut num_allies = 0;
+    for (entity, _) in zone_entities
+        .iter_entities_within_distance(ai_parameters.source.position.position.xy(), distance as f32)
+    {
+        if num_allies >= count {
+            break;
+        }
+
+        if entity == ai_parameters.source.entity || entity == target_entity {
+            continue;
+        }
+
+        let is_ally = ai_world.target_query.get(entity).map_or(
+            false,
+            |(_, team, _, _, health_points, npc)| {
+                team.id == ai_parameters.source.team.id
+                    && health_points.hp > 0
+                    && npc.map_or(false, |npc| {
+                        npc_id.map_or(true, |npc_id| npc.id.get() as i32 == npc_id)
+                    })
+            },
+        );
+        if !is_ally {
+            continue;
+        }
+
+        ai_world
+            .commands
+            .entity(entity)
+            .insert(NextCommand::with_attack(target_entity));
+        num_allies += 1;
+    }
+}
+
+fn ai_action_attack_nearby_entity_by_stat(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    distance: i32,
+    aip_ability_type: AipAbilityType,
+    stat: AipAttackNearbyStat,
+) {
+    let zone_entities = if let Some(zone_entities) = ai_world
+        .client_entity_list
+        .get_zone(ai_parameters.source.position.zone_id)
+    {
+        zone_entities
+    } else {
+        return;
+    };
+
+    let mut selected: Option<(Entity, i32)> = None;
+    for (entity, _) in zone_entities
+        .iter_entities_within_distance(ai_parameters.source.position.position.xy(), distance as f32)
+    {
+        if entity == ai_parameters.source.entity {
+            continue;
+        }
+
+        if let Ok((_, team, ability_values, status_effects, health_points, _)) =
+            ai_world.target_query.get(entity)
+        {
+            if team.id == ai_parameters.source.team.id
+                || health_points.hp == 0
+                || status_effects.is_invisible()
+            {
+                continue;
+            }
+
+            let value = get_aip_ability_value(ability_values, health_points, aip_ability_type);
+            let is_better = selected.map_or(true, |(_, selected_value)| match stat {
+                AipAttackNearbyStat::Lowest => value < selected_value,
+                AipAttackNearbyStat::Highest => value > selected_value,
+            });
+            if is_better {
+                selected = Some((entity, value));
+            }
+        }
+    }
+
+    if let Some((target_entity, _)) = selected {
+        ai_world
+            .commands
+            .entity(ai_parameters.source.entity)
+            .insert(NextCommand::with_attack(target_entity));
+    }
+}
+
+fn get_move_away_direction(
+    ai_world: &mut AiWorld,
+    ai_parameters: &AiParameters,
+    away_from: Option<Point3<f32>>,
+) -> Vector2<f32> {
+    away_from
+        .map(|away_from| ai_parameters.source.position.position.xy() - away_from.xy())
+        .filter(|direction| direction.magnitude_squared() > 0.0)
+        .map(|direction| direction.normalize())
+        .unwrap_or_else(|| {
+            let angle = ai_world.rng.gen_range(0.0..std::f32::consts::TAU);
+            Vector2::new(angle.cos(), angle.sin())
+        })
+}
+
+fn ai_action_run_away(ai_world: &mut AiWorld, ai_parameters: &mut AiParameters, distance: i32) {
+    let away_from = ai_parameters
+        .attacker
+        .map(|attacker| attacker.position.position)
+        .or_else(|| {
+            ai_parameters
+                .source
+                .target
+                .and_then(|target_entity| ai_world.owner_query.get(target_entity).ok())
+                .map(|(position, _)| position.position)
+        });
+    let direction = get_move_away_direction(ai_world, ai_parameters, away_from) * distance as f32;
+    let destination =
+        ai_parameters.source.position.position + Vector3::new(direction.x, direction.y, 0.0);
+
+    ai_world
+        .commands
+        .entity(ai_parameters.source.entity)
+        .insert(NextCommand::with_move(
+            destination,
+            None,
+            Some(MoveMode::Run),
+        ));
+}
+
+fn ai_action_move_distance_from_target(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    move_mode: AipMoveMode,
+    distance: i32,
+) {
+    let target_position = if let Some(target_position) = ai_parameters
+        .source
+        .target
+        .and_then(|target_entity| ai_world.owner_query.get(target_entity).ok())
+        .map(|(position, _)| position.position)
+    {
+        target_position
+    } else {
+        return;
+    };
+
+    let move_mode = match move_mode {
+        AipMoveMode::Run => MoveMode::Run,
+        AipMoveMode::Walk => MoveMode::Walk,
+    };
+    let direction =
+        get_move_away_direction(ai_world, ai_parameters, Some(target_position)) * distance as f32;
+    let destination = target_position + Vector3::new(direction.x, direction.y, 0.0);
+
+    ai_world
+        .commands
+        .entity(ai_parameters.source.entity)
+        .insert(NextCommand::with_move(destination, None, Some(move_mode)));
+}
+
+fn ai_action_message(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    message_type: AipMessageType,
+    string_id: usize,
+) {
+    let message = if let Some(message) = ai_world.game_data.ai.get_ai_string(string_id) {
+        message.to_string()
+    } else {
+        return;
+    };
+
+    match message_type {
+        AipMessageType::Say => {
+            ai_world.server_messages.send_entity_message(
+                ai_parameters.source.client_entity,
+                ServerMessage::LocalChat(LocalChat {
+                    entity_id: ai_parameters.source.client_entity.id,
+                    text: message,
+                }),
+            );
+        }
+        AipMessageType::Shout => {
+            if let Some(npc_data) = ai_world.game_data.npcs.get_npc(ai_parameters.source.npc.id) {
+                ai_world.server_messages.send_entity_message(
+                    ai_parameters.source.client_entity,
+                    ServerMessage::ShoutChat(ShoutChat {
+                        name: npc_data.name.clone(),
+                        text: message,
+                    }),
+                );
+            }
+        }
+        AipMessageType::Announce => {
+            if let Some(npc_data) = ai_world.game_data.npcs.get_npc(ai_parameters.source.npc.id) {
+                ai_world.server_messages.send_zone_message(
+                    ai_parameters.source.position.zone_id,
+                    ServerMessage::AnnounceChat(AnnounceChat {
+                        name: Some(npc_data.name.clone()),
+                        text: message,
+                    }),
+                );
+            }
+        }
+    }
+}
+
+fn ai_action_emote(ai_world: &mut AiWorld, ai_parameters: &mut AiParameters, emote: u8) {
+    ai_world.server_messages.send_entity_message(
+        ai_parameters.source.client_entity,
+        ServerMessage::UseEmote(UseEmote {
+            entity_id: ai_parameters.source.client_entity.id,
+            motion_id: MotionId::new(emote as u16),
+            is_stop: false,
+        }),
+    );
+}
+
+fn ai_action_set_variable(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    variable_type: AipVariableType,
+    variable_id: usize,
+    operator: AipResultOperator,
+    value: i32,
+) {
+    let apply_operator = |current: i32| match operator {
+        AipResultOperator::Set => value,
+        AipResultOperator::Add => current.saturating_add(value),
+        AipResultOperator::Subtract => current.saturating_sub(value),
+    };
+
+    match variable_type {
+        AipVariableType::LocalNpcObject | AipVariableType::Ai => {
+            let object_entity = match variable_type {
+                AipVariableType::LocalNpcObject => ai_parameters.selected_local_npc,
+                _ => Some(ai_parameters.source.entity),
+            };
+
+            if let Some(mut object_variables) = object_entity.and_then(|object_entity| {
+                ai_world.object_variable_query.get_mut(object_entity).ok()
+            }) {
+                if let Some(variable) = object_variables.variables.get_mut(variable_id) {
+                    *variable = apply_operator(*variable);
+                }
+            }
+        }
+        AipVariableType::World => {
+            if let Some(variable) = ai_world.world_variables.world.get_mut(variable_id) {
+                *variable = apply_operator(*variable);
+            }
+        }
+        AipVariableType::Economy => {
+            if let Some(variable) = ai_world.world_variables.economy.get_mut(variable_id) {
+                *variable = apply_operator(*variable);
+            }
+        }
+    }
+}
+
+fn ai_action_do_quest_trigger(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    trigger_name: &str,
+) {
+    if let Some(trigger_entity) = ai_parameters
+        .source
+        .owner
+        .or_else(|| ai_parameters.attacker.map(|attacker| attacker.entity))
+    {
+        ai_world.quest_trigger_events.send(QuestTriggerEvent {
+            trigger_entity,
+            trigger_hash: trigger_name.into(),
+        });
+    }
+}
+
+fn get_aip_zone_id(ai_parameters: &AiParameters, zone_id: Option<AipZoneId>) -> Option<ZoneId> {
+    match zone_id {
+        Some(zone_id) => ZoneId::new(zone_id as u16),
+        None => Some(ai_parameters.source.position.zone_id),
+    }
+}
+
+fn ai_action_set_pvp_flag(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    zone_id: Option<AipZoneId>,
+    enabled: bool,
+) {
+    if let Some(zone_id) = get_aip_zone_id(ai_parameters, zone_id) {
+        if ai_world.zone_list.set_pvp_enabled(zone_id, enabled) {
+            // Clients only receive the pvp flag on join, so update everyone already in the zone
+            ai_world
+                .server_messages
+                .send_zone_message(zone_id, ServerMessage::UpdateZonePvpEnabled(enabled));
+        }
+    }
+}
+
+fn ai_action_set_monster_spawn_state(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    zone_id: Option<AipZoneId>,
+    state: AipMonsterSpawnState,
+) {
+    if let Some(zone_id) = get_aip_zone_id(ai_parameters, zone_id) {
+        let enabled = match state {
+            AipMonsterSpawnState::Disabled => false,
+            AipMonsterSpawnState::Enabled => true,
+            AipMonsterSpawnState::Toggle => !ai_world.zone_list.get_monster_spawns_enabled(zone_id),
+        };
+
+        ai_world
+            .zone_list
+            .set_monster_spawns_enabled(zone_id, enabled);
+    }
+}
+
+fn ai_action_give_item_to_owner(
+    ai_world: &mut AiWorld,
+    ai_parameters: &mut AiParameters,
+    item_reference: ItemReference,
+    quantity: usize,
+) {
+    let owner_entity = if let Some(owner_entity) = ai_parameters.source.owner {
+        owner_entity
+    } else {
+        return;
+    };
+
+    let item = if let Some(item) = Item::new(&item_reference, quantity as u32) {
+        item
+    } else {
+        return;
+    };
+
+    if let Ok((game_client, mut inventory, mut quest_state)) =
+        ai_world.inventory_query.get_mut(owner_entity)
+    {
+        if item_reference.item_type.is_quest_item() {
+            // Quest items are added to the owner's active quests, the client receives
+            // them with the quest data when it next joins a zone
+            if let Err(item) = quest_state.try_add_quest_item(item) {
+                warn!("No active quest has space for quest item {:?}", item);
+            }
+            return;
+        }
+
+        match inventory.try_add_item(item) {
+            Ok((slot, item)) => {
+                game_client
+                    .server_message_tx
+                    .send(ServerMessage::RewardItems(vec![(slot, Some(item.clone()))]))
+                    .ok();
+            }
+            Err(item) => {
+                // Inventory is full, drop the item at the owner's feet
+                if let Ok((owner_position, _)) = ai_world.owner_query.get(owner_entity) {
+                    DroppedItemBundle::spawn(
+                        ai_world.commands,
+                        ai_world.client_entity_list,
+                        DroppedItem::Item(item),
+                        owner_position,
+                        Some(owner_entity),
+                        ai_world.server_time,
+                    );
+                }
+            }
+        }
+    }
+}
+
 fn npc_ai_do_actions(
     ai_program_event: &AipEvent,
     ai_world: &mut AiWorld,
     ai_parameters: &mut AiParameters,
 ) {
     for action in ai_program_event.actions.iter() {
+        if ai_parameters.source.is_trace_enabled {
+            info!(
+                "AI trace {:?}: run action {:?}",
+                ai_parameters.source.entity, action
+            );
+        }
+
         match *action {
             AipAction::Stop => ai_action_stop(ai_world, ai_parameters),
+            AipAction::Emote(emote) => ai_action_emote(ai_world, ai_parameters, emote),
+            AipAction::Say(string_id) => {
+                ai_action_message(ai_world, ai_parameters, AipMessageType::Say, string_id)
+            }
+            AipAction::Message(message_type, string_id) => {
+                ai_action_message(ai_world, ai_parameters, message_type, string_id)
+            }
             AipAction::MoveRandomDistance(move_origin, move_mode, distance) => {
                 ai_action_move_random_distance(
                     ai_world,
@@ -811,14 +1405,91 @@ fn npc_ai_do_actions(
                 }
             }
             AipAction::KillSelf => {
-                // TODO: Fix this, this doesn't send death to clients.
-                ai_world
-                    .commands
-                    .entity(ai_parameters.source.entity)
-                    .insert(HealthPoints::new(0))
-                    .insert(Command::with_die(None, None, None));
+                ai_world.damage_events.send(DamageEvent::with_kill(
+                    ai_parameters.source.entity,
+                    ai_parameters.source.entity,
+                ));
             }
             AipAction::MoveNearOwner => ai_action_move_near_owner(ai_world, ai_parameters),
+            AipAction::SpawnNpc(npc_id, distance, spawn_origin, is_spawn_owner) => {
+                ai_action_spawn_npc(
+                    ai_world,
+                    ai_parameters,
+                    npc_id,
+                    distance,
+                    spawn_origin,
+                    is_spawn_owner,
+                )
+            }
+            AipAction::TransformNpc(npc_id) => {
+                ai_action_transform_npc(ai_world, ai_parameters, npc_id)
+            }
+            AipAction::UseSkill(skill_target, skill_id, motion_id) => {
+                ai_action_use_skill(ai_world, ai_parameters, skill_target, skill_id, motion_id)
+            }
+            AipAction::NearbyAlliesAttackTarget(count, distance, npc_id) => {
+                ai_action_nearby_allies_attack_target(
+                    ai_world,
+                    ai_parameters,
+                    count,
+                    distance,
+                    npc_id,
+                )
+            }
+            AipAction::NearbyAlliesSameNpcAttackTarget(distance) => {
+                let npc_id = ai_parameters.source.npc.id.get() as AipNpcId;
+                ai_action_nearby_allies_attack_target(
+                    ai_world,
+                    ai_parameters,
+                    usize::MAX,
+                    distance,
+                    Some(npc_id),
+                )
+            }
+            AipAction::AttackNearbyEntityByStat(distance, aip_ability_type, stat) => {
+                ai_action_attack_nearby_entity_by_stat(
+                    ai_world,
+                    ai_parameters,
+                    distance,
+                    aip_ability_type,
+                    stat,
+                )
+            }
+            AipAction::RunAway(distance) => ai_action_run_away(ai_world, ai_parameters, distance),
+            AipAction::MoveDistanceFromTarget(move_mode, distance) => {
+                ai_action_move_distance_from_target(ai_world, ai_parameters, move_mode, distance)
+            }
+            AipAction::SetVariable(variable_type, variable_id, operator, value) => {
+                ai_action_set_variable(
+                    ai_world,
+                    ai_parameters,
+                    variable_type,
+                    variable_id,
+                    operator,
+                    value,
+                )
+            }
+            AipAction::DoQuestTrigger(ref trigger_name) => {
+                ai_action_do_quest_trigger(ai_world, ai_parameters, trigger_name)
+            }
+            AipAction::SetPvpFlag(zone_id, enabled) => {
+                ai_action_set_pvp_flag(ai_world, ai_parameters, zone_id, enabled)
+            }
+            AipAction::SetMonsterSpawnState(zone_id, state) => {
+                ai_action_set_monster_spawn_state(ai_world, ai_parameters, zone_id, state)
+            }
+            AipAction::GiveItemToOwner(item_reference, quantity) => {
+                ai_action_give_item_to_owner(ai_world, ai_parameters, item_reference, quantity)
+            }
+            AipAction::SpecialAttack => {
+                // There is no data for a special attack, so we just attack our current target
+                if let Some(target_entity) = ai_parameters.source.target {
+                    ai_world
+                        .commands
+                        .entity(ai_parameters.source.entity)
+                        .insert(NextCommand::with_attack(target_entity));
+                }
+            }
             AipAction::AttackOwnerTarget => {
                 if let Some(owner_target_entity) = ai_parameters
                     .source
@@ -832,26 +1503,6 @@ fn npc_ai_do_actions(
                         .insert(NextCommand::with_attack(owner_target_entity));
                 }
             }
-            /*
-            AipAction::Emote(_) => {}
-            AipAction::Say(_) => {}
-            AipAction::AttackNearbyEntityByStat(_, _, _) => {}
-            AipAction::SpecialAttack => {}
-            AipAction::MoveDistanceFromTarget(_, _) => {}
-            AipAction::TransformNpc(_) => {}
-            AipAction::SpawnNpc(_, _, _, _) => {}
-            AipAction::NearbyAlliesAttackTarget(_, _, _) => {}
-            AipAction::NearbyAlliesSameNpcAttackTarget(_) => {}
-            AipAction::RunAway(_) => {}
-            AipAction::DropRandomItem(_) => {
-            AipAction::UseSkill(_, _, _) => {}
-            AipAction::SetVariable(_, _, _, _) => {}
-            AipAction::Message(_, _) => {}
-            AipAction::DoQuestTrigger(_) => {}
-            AipAction::SetPvpFlag(_, _) => {}
-            AipAction::SetMonsterSpawnState(_, _) => {}
-            AipAction::GiveItemToOwner(_, _) => {}
-            */
             _ => {
                 trace!("Unimplemented AI action: {:?}", action);
             }
@@ -877,9 +1528,22 @@ fn npc_ai_run_trigger(
         is_dead,
     };
 
+    if source.is_trace_enabled {
+        info!(
+            "AI trace {:?}: run trigger \"{}\"",
+            source.entity, ai_trigger.name
+        );
+    }
+
     // Do actions for only the first event with valid conditions
     for ai_program_event in ai_trigger.events.iter() {
         if npc_ai_check_conditions(ai_program_event, ai_world, &mut ai_parameters) {
+            if source.is_trace_enabled {
+                info!(
+                    "AI trace {:?}: matched event \"{}\"",
+                    source.entity, ai_program_event.name
+                );
+            }
             npc_ai_do_actions(ai_program_event, ai_world, &mut ai_parameters);
             break;
         }
@@ -900,7 +1564,7 @@ fn get_attacker_data<'a>(
     {
         Some(AiAttackerData::<'a> {
             entity,
-            _position: attacker_position,
+            position: attacker_position,
             _team: attacker_team,
             ability_values: attacker_ability_values,
             health_points: attacker_health_points,
@@ -930,30 +1594,47 @@ pub fn npc_ai_system(
         Option<&Target>,
         Option<&DamageSources>,
     )>,
-    target_query: Query<(&Level, &Team, &AbilityValues, &StatusEffects, &HealthPoints)>,
+    target_query: Query<(
+        &Level,
+        &Team,
+        &AbilityValues,
+        &StatusEffects,
+        &HealthPoints,
+        Option<&Npc>,
+    )>,
     owner_query: Query<(&Position, Option<&Target>)>,
     object_variable_query: Query<&mut ObjectVariables>,
+    inventory_query: Query<(&GameClient, &mut Inventory, &mut QuestState)>,
     mut spawn_point_query: Query<&mut MonsterSpawnPoint>,
     attacker_query: Query<(&Position, &Level, &Team, &AbilityValues, &HealthPoints)>,
     killer_query: Query<(&Level, &AbilityValues, Option<&GameClient>)>,
     mut client_entity_list: ResMut<ClientEntityList>,
+    mut server_messages: ResMut<ServerMessages>,
     game_data: Res<GameData>,
     server_time: Res<ServerTime>,
     world_rates: Res<WorldRates>,
     world_time: Res<WorldTime>,
-    zone_list: Res<ZoneList>,
+    mut world_variables: ResMut<WorldVariables>,
+    mut zone_list: ResMut<ZoneList>,
+    mut quest_trigger_events: EventWriter<QuestTriggerEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
     mut reward_xp_events: EventWriter<RewardXpEvent>,
 ) {
     let mut ai_world = AiWorld {
         client_entity_list: &mut client_entity_list,
+        server_messages: &mut server_messages,
+        world_variables: &mut world_variables,
+        zone_list: &mut zone_list,
+        quest_trigger_events: &mut quest_trigger_events,
+        damage_events: &mut damage_events,
         commands: &mut commands,
         game_data: &game_data,
         server_time: &server_time,
         world_time: &world_time,
-        zone_list: &zone_list,
         target_query,
         object_variable_query,
         owner_query,
+        inventory_query,
         rng: rand::thread_rng(),
     };
 
@@ -978,14 +1659,17 @@ pub fn npc_ai_system(
             let ai_source_data = AiSourceData {
                 entity,
                 ability_values,
+                client_entity,
                 health_points,
                 level,
+                npc,
                 owner: owner.map(|owner| owner.entity),
                 position,
                 spawn_origin,
                 status_effects,
                 target: target.map(|target| target.entity),
                 team,
+                is_trace_enabled: npc_ai.is_trace_enabled,
             };
 
             if !npc_ai.has_run_created_trigger {
@@ -1244,3 +1928,358 @@ pub fn npc_ai_system(
         },
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use bevy_ecs::{
+        event::Events,
+        prelude::{IntoSystem, World},
+        schedule::{Stage, SystemStage},
+    };
+
+    use super::*;
+    use crate::{
+        data::{formats::AipFile, AiDatabase},
+        game::{
+            components::{CommandAttack, CommandMove},
+            test_utils::{test_ability_values, test_game_data, test_server_time, test_zone_id},
+        },
+    };
+
+    const TEST_AI_INDEX: u16 = 1;
+    const TEST_SOURCE_NPC_ID: u16 = 1;
+    const TEST_ALLY_NPC_ID: u16 = 2;
+    const TEST_OTHER_NPC_ID: u16 = 3;
+    const TEST_MONSTER_TEAM_ID: u32 = Team::DEFAULT_MONSTER_TEAM_ID;
+    const TEST_CHARACTER_TEAM_ID: u32 = Team::DEFAULT_CHARACTER_TEAM_ID;
+
+    // Creates a world where the ai program runs the given actions every time it is idle
+    fn create_world(actions: Vec<AipAction>) -> World {
+        let mut aips = HashMap::new();
+        aips.insert(
+            TEST_AI_INDEX,
+            AipFile {
+                idle_trigger_interval: Duration::from_secs(0),
+                damage_trigger_new_target_chance: 0,
+                trigger_on_created: None,
+                trigger_on_idle: Some(AipTrigger {
+                    name: String::from("on_idle"),
+                    events: vec![AipEvent {
+                        name: String::from("test_event"),
+                        conditions: Vec::new(),
+                        actions,
+                    }],
+                }),
+                trigger_on_attack_move: None,
+                trigger_on_damaged: None,
+                trigger_on_kill: None,
+                trigger_on_dead: None,
+            },
+        );
+
+        let mut game_data = test_game_data();
+        game_data.ai = Arc::new(AiDatabase {
+            strings: HashMap::new(),
+            aips,
+        });
+
+        let mut world = World::new();
+        world.insert_resource(ClientEntityList::new(&game_data.zones));
+        world.insert_resource(game_data);
+        world.insert_resource(ServerMessages::new());
+        world.insert_resource(test_server_time());
+        world.insert_resource(WorldRates::new());
+        world.insert_resource(WorldTime::new());
+        world.insert_resource(WorldVariables::new());
+        world.insert_resource(ZoneList::new());
+        world.insert_resource(Events::<QuestTriggerEvent>::default());
+        world.insert_resource(Events::<DamageEvent>::default());
+        world.insert_resource(Events::<RewardXpEvent>::default());
+        world
+    }
+
+    fn spawn_npc(
+        world: &mut World,
+        npc_id: u16,
+        team_id: u32,
+        level: i32,
+        position: Point3<f32>,
+    ) -> Entity {
+        let mut ability_values = test_ability_values();
+        ability_values.level = level;
+
+        let entity = world
+            .spawn()
+            .insert_bundle((
+                Npc::new(NpcId::new(npc_id).unwrap(), 0),
+                Command::with_stop(),
+                Position::new(position, test_zone_id()),
+                Level::new(level as u32),
+                Team::new(team_id),
+                HealthPoints::new(100),
+                ability_values,
+                StatusEffects::new(),
+            ))
+            .id();
+
+        let client_entity = world
+            .get_resource_mut::<ClientEntityList>()
+            .unwrap()
+            .get_zone_mut(test_zone_id())
+            .unwrap()
+            .join_zone(ClientEntityType::Monster, entity, position)
+            .unwrap();
+        world.entity_mut(entity).insert(client_entity);
+        entity
+    }
+
+    fn spawn_ai_npc(world: &mut World, position: Point3<f32>, target: Option<Entity>) -> Entity {
+        let entity = spawn_npc(
+            world,
+            TEST_SOURCE_NPC_ID,
+            TEST_MONSTER_TEAM_ID,
+            10,
+            position,
+        );
+        world
+            .entity_mut(entity)
+            .insert(NpcAi::new(TEST_AI_INDEX as usize));
+        if let Some(target) = target {
+            world.entity_mut(entity).insert(Target::new(target));
+        }
+        entity
+    }
+
+    fn run_npc_ai(world: &mut World) {
+        let mut stage = SystemStage::single_threaded().with_system(npc_ai_system.system());
+        stage.run(world);
+    }
+
+    fn get_attack_target(world: &World, entity: Entity) -> Option<Entity> {
+        match world.get::<NextCommand>(entity)?.command {
+            Some(CommandData::Attack(CommandAttack { target })) => Some(target),
+            _ => None,
+        }
+    }
+
+    fn get_move_command(world: &World, entity: Entity) -> Option<(Point3<f32>, MoveMode)> {
+        match world.get::<NextCommand>(entity)?.command {
+            Some(CommandData::Move(CommandMove {
+                destination,
+                move_mode: Some(move_mode),
+                ..
+            })) => Some((destination, move_mode)),
+            _ => None,
+        }
+    }
+
+    fn assert_destination(destination: Point3<f32>, expected: Point3<f32>) {
+        assert!(
+            (destination - expected).magnitude() < 0.1,
+            "destination {:?} expected {:?}",
+            destination,
+            expected
+        );
+    }
+
+    #[test]
+    fn nearby_allies_attack_target() {
+        let mut world = create_world(vec![AipAction::NearbyAlliesAttackTarget(
+            10,
+            1000,
+            Some(TEST_ALLY_NPC_ID as i32),
+        )]);
+        let target = spawn_npc(
+            &mut world,
+            TEST_OTHER_NPC_ID,
+            TEST_CHARACTER_TEAM_ID,
+            10,
+            Point3::new(5200.0, 5000.0, 0.0),
+        );
+        let source = spawn_ai_npc(&mut world, Point3::new(5000.0, 5000.0, 0.0), Some(target));
+        let ally_near = spawn_npc(
+            &mut world,
+            TEST_ALLY_NPC_ID,
+            TEST_MONSTER_TEAM_ID,
+            10,
+            Point3::new(5500.0, 5000.0, 0.0),
+        );
+        let ally_far = spawn_npc(
+            &mut world,
+            TEST_ALLY_NPC_ID,
+            TEST_MONSTER_TEAM_ID,
+            10,
+            Point3::new(7000.0, 5000.0, 0.0),
+        );
+        let ally_other_npc_id = spawn_npc(
+            &mut world,
+            TEST_OTHER_NPC_ID,
+            TEST_MONSTER_TEAM_ID,
+            10,
+            Point3::new(5000.0, 5500.0, 0.0),
+        );
+        let enemy_near = spawn_npc(
+            &mut world,
+            TEST_ALLY_NPC_ID,
+            TEST_CHARACTER_TEAM_ID,
+            10,
+            Point3::new(4500.0, 5000.0, 0.0),
+        );
+
+        run_npc_ai(&mut world);
+
+        assert_eq!(get_attack_target(&world, ally_near), Some(target));
+        assert_eq!(get_attack_target(&world, ally_far), None);
+        assert_eq!(get_attack_target(&world, ally_other_npc_id), None);
+        assert_eq!(get_attack_target(&world, enemy_near), None);
+        assert_eq!(get_attack_target(&world, source), None);
+        assert_eq!(get_attack_target(&world, target), None);
+    }
+
+    #[test]
+    fn nearby_allies_attack_target_limited_by_count() {
+        let mut world = create_world(vec![AipAction::NearbyAlliesAttackTarget(1, 1000, None)]);
+        let target = spawn_npc(
+            &mut world,
+            TEST_OTHER_NPC_ID,
+            TEST_CHARACTER_TEAM_ID,
+            10,
+            Point3::new(5200.0, 5000.0, 0.0),
+        );
+        spawn_ai_npc(&mut world, Point3::new(5000.0, 5000.0, 0.0), Some(target));
+        let allies = [
+            spawn_npc(
+                &mut world,
+                TEST_ALLY_NPC_ID,
+                TEST_MONSTER_TEAM_ID,
+                10,
+                Point3::new(5500.0, 5000.0, 0.0),
+            ),
+            spawn_npc(
+                &mut world,
+                TEST_OTHER_NPC_ID,
+                TEST_MONSTER_TEAM_ID,
+                10,
+                Point3::new(5000.0, 5500.0, 0.0),
+            ),
+        ];
+
+        run_npc_ai(&mut world);
+
+        let num_attacking = allies
+            .iter()
+            .filter(|ally| get_attack_target(&world, **ally) == Some(target))
+            .count();
+        assert_eq!(num_attacking, 1);
+    }
+
+    #[test]
+    fn attack_nearby_entity_by_stat() {
+        for &(stat, expected_level) in [
+            (AipAttackNearbyStat::Lowest, 20),
+            (AipAttackNearbyStat::Highest, 30),
+        ]
+        .iter()
+        {
+            let mut world = create_world(vec![AipAction::AttackNearbyEntityByStat(
+                1000,
+                AipAbilityType::Level,
+                stat,
+            )]);
+            let source = spawn_ai_npc(&mut world, Point3::new(5000.0, 5000.0, 0.0), None);
+            spawn_npc(
+                &mut world,
+                TEST_ALLY_NPC_ID,
+                TEST_MONSTER_TEAM_ID,
+                1,
+                Point3::new(5100.0, 5000.0, 0.0),
+            );
+            let enemy_level_20 = spawn_npc(
+                &mut world,
+                TEST_OTHER_NPC_ID,
+                TEST_CHARACTER_TEAM_ID,
+                20,
+                Point3::new(5500.0, 5000.0, 0.0),
+            );
+            let enemy_level_30 = spawn_npc(
+                &mut world,
+                TEST_OTHER_NPC_ID,
+                TEST_CHARACTER_TEAM_ID,
+                30,
+                Point3::new(5000.0, 5600.0, 0.0),
+            );
+            spawn_npc(
+                &mut world,
+                TEST_OTHER_NPC_ID,
+                TEST_CHARACTER_TEAM_ID,
+                50,
+                Point3::new(8000.0, 5000.0, 0.0),
+            );
+
+            run_npc_ai(&mut world);
+
+            let expected_target = if expected_level == 20 {
+                enemy_level_20
+            } else {
+                enemy_level_30
+            };
+            assert_eq!(get_attack_target(&world, source), Some(expected_target));
+        }
+    }
+
+    #[test]
+    fn run_away_from_target() {
+        let mut world = create_world(vec![AipAction::RunAway(1000)]);
+        let target = spawn_npc(
+            &mut world,
+            TEST_OTHER_NPC_ID,
+            TEST_CHARACTER_TEAM_ID,
+            10,
+            Point3::new(4000.0, 5000.0, 0.0),
+        );
+        let source = spawn_ai_npc(&mut world, Point3::new(5000.0, 5000.0, 0.0), Some(target));
+
+        run_npc_ai(&mut world);
+
+        let (destination, move_mode) = get_move_command(&world, source).unwrap();
+        assert_destination(destination, Point3::new(6000.0, 5000.0, 0.0));
+        assert!(matches!(move_mode, MoveMode::Run));
+    }
+
+    #[test]
+    fn move_distance_from_target() {
+        let mut world = create_world(vec![AipAction::MoveDistanceFromTarget(
+            AipMoveMode::Walk,
+            500,
+        )]);
+        let target = spawn_npc(
+            &mut world,
+            TEST_OTHER_NPC_ID,
+            TEST_CHARACTER_TEAM_ID,
+            10,
+            Point3::new(5000.0, 4000.0, 0.0),
+        );
+        let source = spawn_ai_npc(&mut world, Point3::new(5000.0, 7000.0, 0.0), Some(target));
+
+        run_npc_ai(&mut world);
+
+        let (destination, move_mode) = get_move_command(&world, source).unwrap();
+        assert_destination(destination, Point3::new(5000.0, 4500.0, 0.0));
+        assert!(matches!(move_mode, MoveMode::Walk));
+    }
+
+    #[test]
+    fn move_distance_from_target_without_target() {
+        let mut world = create_world(vec![AipAction::MoveDistanceFromTarget(
+            AipMoveMode::Run,
+            500,
+        )]);
+        let source = spawn_ai_npc(&mut world, Point3::new(5000.0, 5000.0, 0.0), None);
+
+        run_npc_ai(&mut world);
+
+        assert!(get_move_command(&world, source).is_none());
+    }
+}
@@ -1,10 +1,10 @@
 use std::collections::HashSet;
 
 use bevy_ecs::prelude::{Entity, EventReader, Mut, Query, Res};
-use log::warn;
+use num_traits::FromPrimitive;
 
 use crate::{
-    data::item::Item,
+    data::{item::Item, AbilityType},
     game::{
         components::{
             AbilityValues, GameClient, Inventory, ItemSlot, Money, Npc, Position, UnionMembership,
@@ -12,7 +12,7 @@ use crate::{
         events::NpcStoreEvent,
         messages::{
             client::NpcStoreBuyItem,
-            server::{NpcStoreTransactionError, ServerMessage},
+            server::{NpcStoreTransactionError, ServerMessage, UpdateAbilityValue},
         },
         resources::WorldRates,
         GameData,
@@ -21,6 +21,11 @@ use crate::{
 
 pub const NPC_STORE_TRANSACTION_MAX_DISTANCE: f32 = 6000.0;
 
+struct NpcStoreTransaction {
+    updated_inventory_slots: HashSet<ItemSlot>,
+    updated_union_points: Option<(AbilityType, u32)>,
+}
+
 fn npc_store_do_transaction(
     npc_query: &Query<(&Npc, &Position)>,
     game_data: &GameData,
@@ -31,8 +36,8 @@ fn npc_store_do_transaction(
     ability_values: &AbilityValues,
     inventory: &mut Mut<Inventory>,
     position: &Position,
-    _union_membership: &UnionMembership,
-) -> Result<HashSet<ItemSlot>, NpcStoreTransactionError> {
+    union_membership: &mut Mut<UnionMembership>,
+) -> Result<NpcStoreTransaction, NpcStoreTransactionError> {
     let (npc, npc_position) = npc_query
         .get(store_entity)
         .map_err(|_| NpcStoreTransactionError::NpcNotFound)?;
@@ -42,9 +47,8 @@ fn npc_store_do_transaction(
         .get_npc(npc.id)
         .ok_or(NpcStoreTransactionError::NpcNotFound)?;
 
-    if npc_data.store_union_number.is_some() {
-        warn!("Unimplemented union NPC store");
-        // if npc_data.store_union_number != union_membership.current_union {
+    let store_union_number = npc_data.store_union_number;
+    if store_union_number.is_some() && store_union_number != union_membership.current_union {
         return Err(NpcStoreTransactionError::NotSameUnion);
     }
 
@@ -55,44 +59,63 @@ fn npc_store_do_transaction(
         return Err(NpcStoreTransactionError::NpcTooFarAway);
     }
 
-    if !sell_items.is_empty() {
-        warn!("Unimplemented selling items to NPC store");
-    }
-
     let mut total_buy_cost = 0i64;
+    let mut total_sell_value = 0i64;
+    let mut total_union_point_cost = 0i64;
     let mut transaction_inventory = inventory.clone();
     let mut updated_inventory_slots = HashSet::new();
 
+    for &(item_slot, quantity) in sell_items {
+        let sell_quantity = match transaction_inventory.get_item(item_slot) {
+            Some(Item::Stackable(_)) if quantity > 0 => quantity as u32,
+            Some(Item::Stackable(_)) => return Err(NpcStoreTransactionError::InvalidItem),
+            Some(Item::Equipment(_)) => 1,
+            None => return Err(NpcStoreTransactionError::InvalidItem),
+        };
+
+        let item = transaction_inventory
+            .try_take_quantity(item_slot, sell_quantity)
+            .ok_or(NpcStoreTransactionError::InvalidItem)?;
+
+        let item_data = game_data
+            .items
+            .get_base_item(item.get_item_reference())
+            .ok_or(NpcStoreTransactionError::InvalidItem)?;
+
+        let item_price = game_data
+            .ability_value_calculator
+            .calculate_npc_store_item_sell_price(
+                &item,
+                item_data,
+                ability_values.get_npc_store_sell_rate(),
+                world_rates.prices_rate,
+            ) as i64;
+
+        updated_inventory_slots.insert(item_slot);
+        total_sell_value += item_price * item.get_quantity() as i64;
+    }
+
     for buy_item in buy_items {
         let store_tab_id = npc_data
             .store_tabs
             .get(buy_item.tab_index)
             .and_then(|x| *x)
-            .ok_or(NpcStoreTransactionError::NpcNotFound)?;
+            .ok_or(NpcStoreTransactionError::InvalidItem)?;
 
         let store_tab_data = game_data
             .npcs
             .get_store_tab(store_tab_id)
-            .ok_or(NpcStoreTransactionError::NpcNotFound)?;
+            .ok_or(NpcStoreTransactionError::InvalidItem)?;
 
         let store_item_reference = *store_tab_data
             .items
             .get(&(buy_item.item_index as u16))
-            .ok_or(NpcStoreTransactionError::NpcNotFound)?;
+            .ok_or(NpcStoreTransactionError::InvalidItem)?;
 
         let item_data = game_data
             .items
             .get_base_item(store_item_reference)
-            .ok_or(NpcStoreTransactionError::NpcNotFound)?;
-
-        let item_price = game_data
-            .ability_value_calculator
-            .calculate_npc_store_item_buy_price(
-                store_item_reference,
-                item_data,
-                ability_values.get_npc_store_buy_rate(),
-                world_rates.prices_rate,
-            ) as i64;
+            .ok_or(NpcStoreTransactionError::InvalidItem)?;
 
         let buy_quantity = if store_item_reference.item_type.is_stackable() {
             buy_item.quantity
@@ -101,22 +124,71 @@ fn npc_store_do_transaction(
         } as i64;
 
         let item = Item::new(&store_item_reference, buy_quantity as u32)
-            .ok_or(NpcStoreTransactionError::NpcNotFound)?;
+            .ok_or(NpcStoreTransactionError::InvalidItem)?;
 
         let (inventory_slot, _) = transaction_inventory
             .try_add_item(item)
-            .map_err(|_| NpcStoreTransactionError::NpcNotFound)?;
+            .map_err(|_| NpcStoreTransactionError::InventoryFull)?;
 
         updated_inventory_slots.insert(inventory_slot);
-        total_buy_cost += item_price * buy_quantity;
+
+        if store_union_number.is_some() {
+            // Union stores charge the item base price in union points
+            total_union_point_cost += item_data.base_price as i64 * buy_quantity;
+        } else {
+            let item_price = game_data
+                .ability_value_calculator
+                .calculate_npc_store_item_buy_price(
+                    store_item_reference,
+                    item_data,
+                    ability_values.get_npc_store_buy_rate(),
+                    world_rates.prices_rate,
+                ) as i64;
+            total_buy_cost += item_price * buy_quantity;
+        }
     }
 
+    let mut updated_union_points = None;
+    if let Some(store_union_number) = store_union_number {
+        let union_index = store_union_number.get() - 1;
+        let current_union_points = *union_membership
+            .points
+            .get(union_index)
+            .ok_or(NpcStoreTransactionError::NotSameUnion)?
+            as i64;
+
+        if current_union_points < total_union_point_cost {
+            return Err(NpcStoreTransactionError::NotEnoughUnionPoints);
+        }
+
+        if total_union_point_cost > 0 {
+            updated_union_points = Some((
+                union_index,
+                (current_union_points - total_union_point_cost) as u32,
+            ));
+        }
+    }
+
+    transaction_inventory
+        .try_add_money(Money(total_sell_value))
+        .map_err(|_| NpcStoreTransactionError::MoneyOverflow)?;
+
     transaction_inventory
         .try_take_money(Money(total_buy_cost))
         .map_err(|_| NpcStoreTransactionError::NotEnoughMoney)?;
 
     **inventory = transaction_inventory;
-    Ok(updated_inventory_slots)
+    let updated_union_points =
+        updated_union_points.and_then(|(union_index, remaining_union_points)| {
+            union_membership.points[union_index] = remaining_union_points;
+            AbilityType::from_usize(AbilityType::UnionPoint1 as usize + union_index)
+                .map(|ability_type| (ability_type, remaining_union_points))
+        });
+
+    Ok(NpcStoreTransaction {
+        updated_inventory_slots,
+        updated_union_points,
+    })
 }
 
 pub fn npc_store_system(
@@ -125,7 +197,7 @@ pub fn npc_store_system(
         &AbilityValues,
         &mut Inventory,
         &Position,
-        &UnionMembership,
+        &mut UnionMembership,
         Option<&GameClient>,
     )>,
     mut npc_store_events: EventReader<NpcStoreEvent>,
@@ -133,7 +205,7 @@ pub fn npc_store_system(
     world_rates: Res<WorldRates>,
 ) {
     for event in npc_store_events.iter() {
-        if let Ok((ability_values, mut inventory, position, union_membership, game_client)) =
+        if let Ok((ability_values, mut inventory, position, mut union_membership, game_client)) =
             transaction_entity_query.get_mut(event.transaction_entity)
         {
             match npc_store_do_transaction(
@@ -146,14 +218,24 @@ pub fn npc_store_system(
                 ability_values,
                 &mut inventory,
                 position,
-                union_membership,
+                &mut union_membership,
             ) {
-                Ok(updated_items) => {
+                Ok(transaction) => {
                     if let Some(game_client) = game_client {
+                        if let Some((ability_type, value)) = transaction.updated_union_points {
+                            game_client
+                                .server_message_tx
+                                .send(ServerMessage::UpdateAbilityValue(
+                                    UpdateAbilityValue::RewardSet(ability_type, value as i32),
+                                ))
+                                .ok();
+                        }
+
                         game_client
                             .server_message_tx
                             .send(ServerMessage::UpdateInventory(
-                                updated_items
+                                transaction
+                                    .updated_inventory_slots
                                     .iter()
                                     .map(|slot| (*slot, inventory.get_item(*slot).cloned()))
                                     .collect(),
@@ -175,3 +257,361 @@ pub fn npc_store_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use bevy_ecs::{
+        event::Events,
+        prelude::{IntoSystem, World},
+        schedule::{Stage, SystemStage},
+    };
+    use nalgebra::Point3;
+    use tokio::sync::mpsc::UnboundedReceiver;
+
+    use super::*;
+    use crate::{
+        data::{
+            item::{ItemType, StackableItem},
+            ItemReference, NpcDatabase, NpcId, NpcStoreTabData, NpcStoreTabId,
+        },
+        game::{
+            components::InventoryPageType,
+            test_utils::{
+                test_ability_values, test_base_item_data, test_game_data, test_item_database,
+                test_npc_data, test_zone_id,
+            },
+        },
+    };
+
+    const TEST_STORE_NPC_ID: u16 = 1;
+    const TEST_STORE_TAB_ID: u16 = 1;
+    const TEST_STORE_ITEM_PRICE: u32 = 100;
+    const TEST_STORE_CHEAP_ITEM_PRICE: u32 = 50;
+    const TEST_SELL_ITEM_PRICE: u32 = 1000;
+
+    // Store tab item index 0 and 1 sell these materials, the player owns TEST_SELL_ITEM
+    const TEST_STORE_ITEM: usize = 1;
+    const TEST_STORE_CHEAP_ITEM: usize = 2;
+    const TEST_SELL_ITEM: usize = 9;
+
+    struct TestStore {
+        world: World,
+        store_entity: Entity,
+        character_entity: Entity,
+        server_message_rx: UnboundedReceiver<ServerMessage>,
+    }
+
+    fn material(item_number: usize, quantity: u32) -> Item {
+        Item::Stackable(
+            StackableItem::new(
+                &ItemReference::new(ItemType::Material, item_number),
+                quantity,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn create_test_store(inventory: Inventory) -> TestStore {
+        let mut npc_data = test_npc_data(NpcId::new(TEST_STORE_NPC_ID).unwrap());
+        npc_data.store_tabs[0] = NpcStoreTabId::new(TEST_STORE_TAB_ID);
+
+        let mut store_tab_items = HashMap::new();
+        store_tab_items.insert(0, ItemReference::new(ItemType::Material, TEST_STORE_ITEM));
+        store_tab_items.insert(
+            1,
+            ItemReference::new(ItemType::Material, TEST_STORE_CHEAP_ITEM),
+        );
+
+        let mut npcs = HashMap::new();
+        npcs.insert(TEST_STORE_NPC_ID, npc_data);
+        let mut store_tabs = HashMap::new();
+        store_tabs.insert(
+            TEST_STORE_TAB_ID,
+            NpcStoreTabData {
+                name: String::from("Test Store Tab"),
+                items: store_tab_items,
+            },
+        );
+
+        let mut game_data = test_game_data();
+        game_data.npcs = Arc::new(NpcDatabase::new(npcs, HashMap::new(), store_tabs));
+        game_data.items = Arc::new(test_item_database(vec![
+            (
+                TEST_STORE_ITEM as u16,
+                test_base_item_data(TEST_STORE_ITEM_PRICE),
+            ),
+            (
+                TEST_STORE_CHEAP_ITEM as u16,
+                test_base_item_data(TEST_STORE_CHEAP_ITEM_PRICE),
+            ),
+            (
+                TEST_SELL_ITEM as u16,
+                test_base_item_data(TEST_SELL_ITEM_PRICE),
+            ),
+        ]));
+
+        let mut world = World::new();
+        world.insert_resource(game_data);
+        world.insert_resource(WorldRates::new());
+        world.insert_resource(Events::<NpcStoreEvent>::default());
+
+        let store_entity = world
+            .spawn()
+            .insert_bundle((
+                Npc::new(NpcId::new(TEST_STORE_NPC_ID).unwrap(), 0),
+                Position::new(Point3::new(5000.0, 5000.0, 0.0), test_zone_id()),
+            ))
+            .id();
+
+        let (_, client_message_rx) = crossbeam_channel::unbounded();
+        let (server_message_tx, server_message_rx) = tokio::sync::mpsc::unbounded_channel();
+        let character_entity = world
+            .spawn()
+            .insert_bundle((
+                test_ability_values(),
+                inventory,
+                Position::new(Point3::new(5500.0, 5000.0, 0.0), test_zone_id()),
+                UnionMembership::new(),
+                GameClient::new(client_message_rx, server_message_tx),
+            ))
+            .id();
+
+        TestStore {
+            world,
+            store_entity,
+            character_entity,
+            server_message_rx,
+        }
+    }
+
+    fn run_transaction(
+        test_store: &mut TestStore,
+        buy_items: Vec<NpcStoreBuyItem>,
+        sell_items: Vec<(ItemSlot, usize)>,
+    ) {
+        let event = NpcStoreEvent {
+            store_entity: test_store.store_entity,
+            transaction_entity: test_store.character_entity,
+            buy_items,
+            sell_items,
+        };
+        test_store
+            .world
+            .get_resource_mut::<Events<NpcStoreEvent>>()
+            .unwrap()
+            .send(event);
+
+        let mut stage = SystemStage::single_threaded().with_system(npc_store_system.system());
+        stage.run(&mut test_store.world);
+    }
+
+    fn get_inventory(test_store: &TestStore) -> &Inventory {
+        test_store
+            .world
+            .get::<Inventory>(test_store.character_entity)
+            .unwrap()
+    }
+
+    fn get_quantity(inventory: &Inventory, item_number: usize) -> u32 {
+        inventory
+            .find_item(ItemReference::new(ItemType::Material, item_number))
+            .and_then(|slot| inventory.get_item(slot))
+            .map_or(0, |item| item.get_quantity())
+    }
+
+    fn buy_item(item_index: usize, quantity: usize) -> NpcStoreBuyItem {
+        NpcStoreBuyItem {
+            tab_index: 0,
+            item_index,
+            quantity,
+        }
+    }
+
+    fn material_slot(index: usize) -> ItemSlot {
+        ItemSlot::Inventory(InventoryPageType::Materials, index)
+    }
+
+    fn assert_transaction_error(test_store: &mut TestStore, expected: NpcStoreTransactionError) {
+        match test_store.server_message_rx.try_recv() {
+            Ok(ServerMessage::NpcStoreTransactionError(error)) => assert_eq!(
+                std::mem::discriminant(&error),
+                std::mem::discriminant(&expected)
+            ),
+            _ => panic!("expected NpcStoreTransactionError"),
+        }
+    }
+
+    fn inventory_with_money(money: i64) -> Inventory {
+        let mut inventory = Inventory::new();
+        inventory.money = Money(money);
+        inventory
+    }
+
+    fn inventory_with_sell_item(money: i64, quantity: u32) -> Inventory {
+        let mut inventory = inventory_with_money(money);
+        inventory
+            .try_add_item(material(TEST_SELL_ITEM, quantity))
+            .ok()
+            .unwrap();
+        inventory
+    }
+
+    #[test]
+    fn buy_items() {
+        let mut test_store = create_test_store(inventory_with_money(1000));
+
+        run_transaction(
+            &mut test_store,
+            vec![buy_item(0, 5), buy_item(1, 2)],
+            Vec::new(),
+        );
+
+        let inventory = get_inventory(&test_store);
+        assert_eq!(get_quantity(inventory, TEST_STORE_ITEM), 5);
+        assert_eq!(get_quantity(inventory, TEST_STORE_CHEAP_ITEM), 2);
+        assert_eq!(
+            inventory.money.0,
+            1000 - 5 * TEST_STORE_ITEM_PRICE as i64 - 2 * TEST_STORE_CHEAP_ITEM_PRICE as i64
+        );
+        assert!(matches!(
+            test_store.server_message_rx.try_recv(),
+            Ok(ServerMessage::UpdateInventory(..))
+        ));
+    }
+
+    #[test]
+    fn sell_items() {
+        let mut test_store = create_test_store(inventory_with_sell_item(0, 3));
+
+        run_transaction(&mut test_store, Vec::new(), vec![(material_slot(0), 2)]);
+
+        let inventory = get_inventory(&test_store);
+        assert_eq!(get_quantity(inventory, TEST_SELL_ITEM), 1);
+        assert_eq!(inventory.money.0, 2 * (TEST_SELL_ITEM_PRICE as i64 / 2));
+    }
+
+    #[test]
+    fn buy_and_sell_items() {
+        // The money from the sold items pays for the bought items
+        let mut test_store = create_test_store(inventory_with_sell_item(0, 3));
+
+        run_transaction(
+            &mut test_store,
+            vec![buy_item(1, 10)],
+            vec![(material_slot(0), 3)],
+        );
+
+        let inventory = get_inventory(&test_store);
+        assert_eq!(get_quantity(inventory, TEST_SELL_ITEM), 0);
+        assert_eq!(get_quantity(inventory, TEST_STORE_CHEAP_ITEM), 10);
+        assert_eq!(
+            inventory.money.0,
+            3 * (TEST_SELL_ITEM_PRICE as i64 / 2) - 10 * TEST_STORE_CHEAP_ITEM_PRICE as i64
+        );
+    }
+
+    #[test]
+    fn buy_not_enough_money() {
+        let mut test_store = create_test_store(inventory_with_sell_item(100, 1));
+
+        run_transaction(
+            &mut test_store,
+            vec![buy_item(0, 5)],
+            vec![(material_slot(0), 1)],
+        );
+
+        // Failed transaction must not modify the inventory
+        let inventory = get_inventory(&test_store);
+        assert_eq!(inventory.money.0, 100);
+        assert_eq!(get_quantity(inventory, TEST_SELL_ITEM), 1);
+        assert_eq!(get_quantity(inventory, TEST_STORE_ITEM), 0);
+        assert_transaction_error(&mut test_store, NpcStoreTransactionError::NotEnoughMoney);
+    }
+
+    #[test]
+    fn buy_invalid_store_item() {
+        let mut test_store = create_test_store(inventory_with_money(1000));
+
+        run_transaction(&mut test_store, vec![buy_item(5, 1)], Vec::new());
+
+        assert_eq!(get_inventory(&test_store).money.0, 1000);
+        assert_transaction_error(&mut test_store, NpcStoreTransactionError::InvalidItem);
+    }
+
+    #[test]
+    fn buy_inventory_full() {
+        let mut inventory = inventory_with_money(1000);
+        for slot in inventory.materials.slots.iter_mut() {
+            *slot = Some(material(TEST_SELL_ITEM, 1));
+        }
+        let mut test_store = create_test_store(inventory);
+
+        run_transaction(&mut test_store, vec![buy_item(0, 1)], Vec::new());
+
+        assert_eq!(get_inventory(&test_store).money.0, 1000);
+        assert_transaction_error(&mut test_store, NpcStoreTransactionError::InventoryFull);
+    }
+
+    #[test]
+    fn sell_empty_slot() {
+        let mut test_store = create_test_store(inventory_with_sell_item(0, 1));
+
+        run_transaction(&mut test_store, Vec::new(), vec![(material_slot(1), 1)]);
+
+        assert_eq!(get_inventory(&test_store).money.0, 0);
+        assert_transaction_error(&mut test_store, NpcStoreTransactionError::InvalidItem);
+    }
+
+    #[test]
+    fn sell_more_than_owned() {
+        let mut test_store = create_test_store(inventory_with_sell_item(0, 1));
+
+        run_transaction(&mut test_store, Vec::new(), vec![(material_slot(0), 2)]);
+
+        let inventory = get_inventory(&test_store);
+        assert_eq!(inventory.money.0, 0);
+        assert_eq!(get_quantity(inventory, TEST_SELL_ITEM), 1);
+        assert_transaction_error(&mut test_store, NpcStoreTransactionError::InvalidItem);
+    }
+
+    #[test]
+    fn sell_zero_quantity() {
+        let mut test_store = create_test_store(inventory_with_sell_item(0, 1));
+
+        run_transaction(&mut test_store, Vec::new(), vec![(material_slot(0), 0)]);
+
+        let inventory = get_inventory(&test_store);
+        assert_eq!(inventory.money.0, 0);
+        assert_eq!(get_quantity(inventory, TEST_SELL_ITEM), 1);
+        assert_transaction_error(&mut test_store, NpcStoreTransactionError::InvalidItem);
+    }
+
+    #[test]
+    fn sell_money_overflow() {
+        let mut test_store = create_test_store(inventory_with_sell_item(i64::MAX - 1, 1));
+
+        run_transaction(&mut test_store, Vec::new(), vec![(material_slot(0), 1)]);
+
+        let inventory = get_inventory(&test_store);
+        assert_eq!(inventory.money.0, i64::MAX - 1);
+        assert_eq!(get_quantity(inventory, TEST_SELL_ITEM), 1);
+        assert_transaction_error(&mut test_store, NpcStoreTransactionError::MoneyOverflow);
+    }
+
+    #[test]
+    fn store_too_far_away() {
+        let mut test_store = create_test_store(inventory_with_money(1000));
+        test_store
+            .world
+            .get_mut::<Position>(test_store.character_entity)
+            .unwrap()
+            .position = Point3::new(50000.0, 5000.0, 0.0);
+
+        run_transaction(&mut test_store, vec![buy_item(0, 1)], Vec::new());
+
+        assert_eq!(get_inventory(&test_store).money.0, 1000);
+        assert_transaction_error(&mut test_store, NpcStoreTransactionError::NpcTooFarAway);
+    }
+}
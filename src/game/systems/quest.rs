@@ -1,4 +1,4 @@
-use std::{num::NonZeroU8, ops::RangeInclusive};
+use std::{convert::TryFrom, num::NonZeroU8, ops::RangeInclusive, time::Duration};
 
 use bevy_ecs::prelude::{Commands, Entity, EventReader, EventWriter, Mut, Query, Res, ResMut};
 use chrono::{Datelike, Timelike};
@@ -19,12 +19,14 @@ use crate::{
             QsdVariableType, QsdZoneId,
         },
         item::{EquipmentItem, Item},
-        AbilityType, ItemReference, NpcId, QuestTrigger, SkillId, WorldTicks, ZoneId,
+        AbilityType, ItemReference, NpcId, QuestTrigger, QuestTriggerHash, SkillId, WorldTicks,
+        ZoneId,
     },
     game::{
         bundles::{
             ability_values_add_value, ability_values_get_value, ability_values_set_value,
             client_entity_teleport_zone, skill_list_try_learn_skill, MonsterBundle,
+            SkillListLearnRequirements,
         },
         components::{
             AbilityValues, ActiveQuest, BasicStats, CharacterInfo, ClientEntity, Equipment,
@@ -33,9 +35,13 @@ use crate::{
             SkillPoints, SpawnOrigin, Stamina, StatPoints, Team, UnionMembership,
         },
         events::{QuestTriggerEvent, RewardXpEvent},
-        messages::server::{AnnounceChat, LocalChat, QuestTriggerResult, ServerMessage, ShoutChat},
+        messages::server::{
+            AnnounceChat, LocalChat, QuestTriggerResult, ServerMessage, ShoutChat, Whisper,
+        },
         resources::{
-            ClientEntityList, ServerMessages, ServerTime, WorldRates, WorldTime, ZoneList,
+            ClientEntityList, PendingQuestTriggerList, PendingQuestTriggerTarget, ScriptContext,
+            ScriptEffect, ScriptEngine, ServerMessages, ServerTime, WorldRates, WorldTime,
+            WorldVariables, ZoneList,
         },
         GameData,
     },
@@ -74,15 +80,18 @@ struct QuestParameters<'a, 'world, 'b> {
     next_trigger_name: Option<String>,
 }
 
-struct QuestWorld<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> {
+struct QuestWorld<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k> {
     commands: &'a mut Commands<'b>,
     client_entity_list: &'a mut ResMut<'c, ClientEntityList>,
     game_data: &'a GameData,
+    script_engine: &'a ScriptEngine,
     server_messages: &'a mut ResMut<'i, ServerMessages>,
     server_time: &'a ServerTime,
     world_rates: &'a WorldRates,
     world_time: &'a WorldTime,
+    world_variables: &'a mut ResMut<'k, WorldVariables>,
     zone_list: &'a mut ResMut<'h, ZoneList>,
+    pending_quest_trigger_list: &'a mut ResMut<'j, PendingQuestTriggerList>,
     reward_xp_events: &'a mut EventWriter<'d, RewardXpEvent>,
     object_variables_query: &'a mut Query<'e, (&'f mut ObjectVariables, &'g Position)>,
     rng: ThreadRng,
@@ -526,11 +535,32 @@ fn quest_condition_compare_npc_object_variables(
     quest_condition_operator(operator, value1, value2)
 }
 
+fn quest_trigger_unimplemented<T: std::fmt::Debug>(
+    quest_trigger: &QuestTrigger,
+    value: &T,
+) -> bool {
+    warn!(
+        "Quest trigger {} ({:?}) failed, unimplemented {:?}",
+        quest_trigger.name,
+        QuestTriggerHash::from(quest_trigger.name.as_str()),
+        value
+    );
+    false
+}
+
 fn quest_trigger_check_conditions(
     quest_world: &mut QuestWorld,
     quest_parameters: &mut QuestParameters,
     quest_trigger: &QuestTrigger,
 ) -> bool {
+    if let Some(condition) = quest_trigger
+        .conditions
+        .iter()
+        .find(|condition| !is_quest_condition_implemented(condition))
+    {
+        return quest_trigger_unimplemented(quest_trigger, condition);
+    }
+
     for condition in quest_trigger.conditions.iter() {
         let result = match *condition {
             QsdCondition::AbilityValue(ref ability_values) => {
@@ -627,24 +657,8 @@ fn quest_trigger_check_conditions(
                 // Random percent is only checked on client
                 true
             }
-            _ => {
-                warn!("Unimplemented quest condition: {:?}", condition);
-                false
-            } /*
-              // TODO: Implement party system
-              QsdCondition::Party(_) => todo!(),
-              QsdCondition::PartyMemberCount(_) => todo!(),
-
-              // TODO: Implement clan system
-              QsdCondition::InClan(_) => todo!(),
-              QsdCondition::ClanPosition(_, _) => todo!(),
-              QsdCondition::ClanPointContribution(_, _) => todo!(),
-              QsdCondition::ClanLevel(_, _) => todo!(),
-              QsdCondition::ClanPoints(_, _) => todo!(),
-              QsdCondition::ClanMoney(_, _) => todo!(),
-              QsdCondition::ClanMemberCount(_, _) => todo!(),
-              QsdCondition::HasClanSkill(_, _) => todo!(),
-              */
+            // Rejected above by is_quest_condition_implemented
+            _ => quest_trigger_unimplemented(quest_trigger, condition),
         };
 
         if !result {
@@ -1021,10 +1035,35 @@ fn quest_reward_add_skill(
 
     if let Some(skill_list) = quest_parameters.source.skill_list.as_deref_mut() {
         skill_list_try_learn_skill(
-            quest_world.game_data.skills.as_ref(),
+            quest_world.game_data,
             skill_id,
             skill_list,
             quest_parameters.source.skill_points.as_deref_mut(),
+            &SkillListLearnRequirements {
+                ability_values: quest_parameters.source.ability_values,
+                level: quest_parameters.source.level,
+                move_speed: quest_parameters.source.move_speed,
+                position: quest_parameters.source.position,
+                team: quest_parameters.source.team,
+                character_info: quest_parameters
+                    .source
+                    .character_info
+                    .as_deref()
+                    .map(|x| &**x),
+                experience_points: quest_parameters
+                    .source
+                    .experience_points
+                    .as_deref()
+                    .map(|x| &**x),
+                inventory: quest_parameters.source.inventory.as_deref().map(|x| &**x),
+                stamina: quest_parameters.source.stamina.as_deref().map(|x| &**x),
+                stat_points: quest_parameters.source.stat_points.as_deref().map(|x| &**x),
+                union_membership: quest_parameters
+                    .source
+                    .union_membership
+                    .as_deref()
+                    .map(|x| &**x),
+            },
             quest_parameters.source.game_client,
         )
         .ok()
@@ -1111,9 +1150,14 @@ fn quest_reward_reset_skills(
 fn quest_reward_teleport(
     quest_world: &mut QuestWorld,
     quest_parameters: &mut QuestParameters,
-    new_zone_id: ZoneId,
+    new_zone_id: QsdZoneId,
     new_position: Point3<f32>,
 ) -> bool {
+    let new_zone_id = match ZoneId::new(new_zone_id as u16) {
+        Some(zone_id) if quest_world.client_entity_list.get_zone(zone_id).is_some() => zone_id,
+        _ => return false,
+    };
+
     client_entity_teleport_zone(
         quest_world.commands,
         quest_world.client_entity_list,
@@ -1126,6 +1170,66 @@ fn quest_reward_teleport(
     true
 }
 
+fn quest_reward_trigger_after_delay_for_object(
+    quest_world: &mut QuestWorld,
+    quest_parameters: &mut QuestParameters,
+    object_type: QsdObjectType,
+    delay: Duration,
+    trigger_name: &str,
+) -> bool {
+    let (trigger_entity, selected_npc, selected_event_object) = match object_type {
+        // Event objects do not have the components required to be the source of a quest
+        // trigger, so it runs for the player with the event object selected, which is all
+        // the event object variable conditions and rewards need.
+        QsdObjectType::Event => match quest_parameters.selected_event_object {
+            Some(_) => (
+                quest_parameters.source.entity,
+                None,
+                quest_parameters.selected_event_object,
+            ),
+            None => return false,
+        },
+        // The npc runs the trigger itself, the player may have left by the time it fires
+        QsdObjectType::Npc => match quest_parameters.selected_npc {
+            Some(npc_entity) => (npc_entity, Some(npc_entity), None),
+            None => return false,
+        },
+        QsdObjectType::Owner => (quest_parameters.source.entity, None, None),
+    };
+
+    quest_world.pending_quest_trigger_list.push(
+        quest_world.server_time.now + delay,
+        trigger_name.into(),
+        PendingQuestTriggerTarget::Entity {
+            trigger_entity,
+            selected_npc,
+            selected_event_object,
+        },
+    );
+    true
+}
+
+fn quest_reward_trigger_for_zone_team(
+    quest_world: &mut QuestWorld,
+    zone_id: QsdZoneId,
+    team_number: QsdTeamNumber,
+    trigger_name: &str,
+) -> bool {
+    if let Some(zone_id) = ZoneId::new(zone_id as u16) {
+        quest_world.pending_quest_trigger_list.push(
+            quest_world.server_time.now,
+            trigger_name.into(),
+            PendingQuestTriggerTarget::ZoneTeam {
+                zone_id,
+                team_number: team_number as u32,
+            },
+        );
+        true
+    } else {
+        false
+    }
+}
+
 fn quest_reward_ability_value(
     quest_parameters: &mut QuestParameters,
     reward_operator: QsdRewardOperator,
@@ -1366,6 +1470,253 @@ fn quest_reward_clear_switch_group(quest_parameters: &mut QuestParameters, group
     }
 }
 
+fn quest_script_effects_valid(
+    quest_world: &QuestWorld,
+    quest_parameters: &QuestParameters,
+    function_name: &str,
+    effects: &[ScriptEffect],
+) -> bool {
+    let mut money = quest_parameters
+        .source
+        .inventory
+        .as_ref()
+        .map(|inventory| inventory.money);
+
+    for effect in effects {
+        let valid = match effect {
+            ScriptEffect::AddExperience(_)
+            | ScriptEffect::Announce(_)
+            | ScriptEffect::Whisper(_) => true,
+            ScriptEffect::AddItem(item_reference, quantity) => {
+                Item::new(item_reference, *quantity).is_some()
+                    && if item_reference.item_type.is_quest_item() {
+                        quest_parameters
+                            .source
+                            .quest_state
+                            .as_ref()
+                            .zip(quest_parameters.selected_quest_index)
+                            .map_or(false, |(quest_state, selected_quest_index)| {
+                                quest_state.get_quest(selected_quest_index).is_some()
+                            })
+                    } else {
+                        quest_parameters.source.inventory.is_some()
+                    }
+            }
+            ScriptEffect::AddMoney(amount) => {
+                if i32::try_from(*amount).is_err() {
+                    warn!(
+                        "Script function {} tried to add money {} which does not fit in an i32",
+                        function_name, amount
+                    );
+                    false
+                } else if let Some(total) =
+                    money.and_then(|money| money.0.checked_add(*amount).map(Money))
+                {
+                    money = Some(total);
+                    true
+                } else {
+                    false
+                }
+            }
+            ScriptEffect::SetQuestSwitch(switch_id, _) => quest_parameters
+                .source
+                .quest_state
+                .as_ref()
+                .map_or(false, |quest_state| {
+                    *switch_id < quest_state.quest_switches.len()
+                }),
+            ScriptEffect::SetWorldVariable(index, _) => {
+                *index < quest_world.world_variables.world.len()
+            }
+        };
+
+        if !valid {
+            warn!(
+                "Script function {} produced an invalid effect",
+                function_name
+            );
+            return false;
+        }
+    }
+
+    true
+}
+
+fn quest_reward_call_script(
+    quest_world: &mut QuestWorld,
+    quest_parameters: &mut QuestParameters,
+    function_name: &str,
+) -> bool {
+    if !quest_world.script_engine.has_function(function_name) {
+        // Without a server script this function is for client side only.
+        return true;
+    }
+
+    let name = match quest_parameters.source.character_info.as_ref() {
+        Some(character_info) => character_info.name.clone(),
+        None => return false,
+    };
+    let money = quest_parameters
+        .source
+        .inventory
+        .as_ref()
+        .map_or(0, |inventory| inventory.money.0);
+    let script_context = ScriptContext::new(
+        name,
+        quest_parameters.source.level.level as i64,
+        money,
+        quest_parameters.source.position.zone_id.get() as i64,
+        quest_world.world_variables.world.clone(),
+    );
+
+    let result = match quest_world
+        .script_engine
+        .call(function_name, &script_context)
+    {
+        Ok(result) => result,
+        Err(error) => {
+            warn!("Script function {} failed, error: {}", function_name, error);
+            false
+        }
+    };
+    if !result {
+        return false;
+    }
+
+    // Scripts are untrusted, check every effect before applying any of them
+    let effects = script_context.take_effects();
+    if !quest_script_effects_valid(quest_world, quest_parameters, function_name, &effects) {
+        return false;
+    }
+
+    for effect in effects {
+        let success = match effect {
+            ScriptEffect::AddExperience(xp) => {
+                quest_world.reward_xp_events.send(RewardXpEvent::new(
+                    quest_parameters.source.entity,
+                    xp,
+                    0,
+                    None,
+                ));
+                true
+            }
+            ScriptEffect::AddItem(item_reference, quantity) => {
+                quest_reward_add_item(quest_parameters, item_reference, quantity as usize)
+            }
+            ScriptEffect::AddMoney(amount) => i32::try_from(amount).map_or(false, |amount| {
+                quest_reward_ability_value(
+                    quest_parameters,
+                    QsdRewardOperator::Add,
+                    AbilityType::Money,
+                    amount,
+                )
+            }),
+            ScriptEffect::Announce(text) => {
+                quest_world.server_messages.send_zone_message(
+                    quest_parameters.source.position.zone_id,
+                    ServerMessage::AnnounceChat(AnnounceChat { name: None, text }),
+                );
+                true
+            }
+            ScriptEffect::SetQuestSwitch(switch_id, value) => {
+                quest_reward_set_quest_switch(quest_parameters, switch_id, value)
+            }
+            ScriptEffect::SetWorldVariable(index, value) => {
+                if let Some(variable) = quest_world.world_variables.world.get_mut(index) {
+                    *variable = value;
+                    true
+                } else {
+                    false
+                }
+            }
+            ScriptEffect::Whisper(text) => {
+                if let Some(game_client) = quest_parameters.source.game_client {
+                    game_client
+                        .server_message_tx
+                        .send(ServerMessage::Whisper(Whisper {
+                            from: String::from("SERVER"),
+                            text,
+                        }))
+                        .ok();
+                }
+                true
+            }
+        };
+
+        if !success {
+            warn!(
+                "Script function {} produced an invalid effect",
+                function_name
+            );
+            return false;
+        }
+    }
+
+    true
+}
+
+fn quest_reward_format_announce_message(
+    quest_world: &mut QuestWorld,
+    quest_parameters: &mut QuestParameters,
+    string_id: usize,
+    npc_variables: &[(QsdNpcId, QsdVariableId)],
+) -> bool {
+    let format = match quest_world
+        .game_data
+        .quests
+        .get_quest_string(string_id as u16)
+    {
+        Some(format) => format,
+        None => return false,
+    };
+
+    // Each %d placeholder is replaced in order by the value of a NPC variable
+    let mut message = String::with_capacity(format.len());
+    let mut npc_variables = npc_variables.iter();
+    let mut remaining = format.as_str();
+    while let Some(index) = remaining.find("%d") {
+        message.push_str(&remaining[..index]);
+
+        if let Some(&(npc_id, variable_id)) = npc_variables.next() {
+            let value = NpcId::new(npc_id as u16)
+                .and_then(|npc_id| quest_world.zone_list.find_npc(npc_id))
+                .and_then(|npc_entity| quest_world.object_variables_query.get_mut(npc_entity).ok())
+                .and_then(|(object_variables, _)| {
+                    object_variables.variables.get(variable_id).cloned()
+                })
+                .unwrap_or(0);
+            message.push_str(&value.to_string());
+        } else {
+            message.push_str("%d");
+        }
+
+        remaining = &remaining[index + 2..];
+    }
+    message.push_str(remaining);
+
+    quest_world.server_messages.send_zone_message(
+        quest_parameters.source.position.zone_id,
+        ServerMessage::AnnounceChat(AnnounceChat {
+            name: None,
+            text: message,
+        }),
+    );
+    true
+}
+
+fn quest_reward_set_revive_position(
+    quest_parameters: &mut QuestParameters,
+    position: &Point2<f32>,
+) -> bool {
+    if let Some(character_info) = quest_parameters.source.character_info.as_mut() {
+        character_info.revive_zone_id = quest_parameters.source.position.zone_id;
+        character_info.revive_position = Point3::new(position.x, position.y, 0.0);
+        true
+    } else {
+        false
+    }
+}
+
 fn quest_reward_set_team_number(
     quest_parameters: &mut QuestParameters,
     source: QsdRewardSetTeamNumberSource,
@@ -1440,9 +1791,8 @@ fn quest_reward_npc_message(
                 );
             }
             QsdRewardNpcMessageType::Shout => {
-                // TODO: A shout message actually goes to adjacent 3 sectors rather than full zone
-                quest_world.server_messages.send_zone_message(
-                    quest_parameters.source.position.zone_id,
+                quest_world.server_messages.send_entity_message(
+                    quest_parameters.source.client_entity,
                     ServerMessage::ShoutChat(ShoutChat {
                         name,
                         text: message.clone(),
@@ -1469,6 +1819,15 @@ fn quest_trigger_apply_rewards(
     quest_parameters: &mut QuestParameters,
     quest_trigger: &QuestTrigger,
 ) -> bool {
+    // Check every reward before applying any, so a trigger never half applies
+    if let Some(reward) = quest_trigger
+        .rewards
+        .iter()
+        .find(|reward| !is_quest_reward_implemented(reward))
+    {
+        return quest_trigger_unimplemented(quest_trigger, reward);
+    }
+
     for reward in quest_trigger.rewards.iter() {
         let result = match *reward {
             QsdReward::Quest(ref action) => {
@@ -1541,14 +1900,13 @@ fn quest_trigger_apply_rewards(
                     base_reward_value,
                 )
             }
-            QsdReward::CallLuaFunction(_) => {
-                // CallLuaFunction is for client side only.
-                true
+            QsdReward::CallLuaFunction(ref function_name) => {
+                quest_reward_call_script(quest_world, quest_parameters, function_name)
             }
             QsdReward::Teleport(_reward_target, zone_id, ref position) => quest_reward_teleport(
                 quest_world,
                 quest_parameters,
-                ZoneId::new(zone_id as u16).unwrap(),
+                zone_id,
                 Point3::new(position.x, position.y, 0.0),
             ),
             QsdReward::Trigger(ref name) => {
@@ -1615,25 +1973,31 @@ fn quest_trigger_apply_rewards(
             QsdReward::NpcMessage(message_type, string_id) => {
                 quest_reward_npc_message(quest_world, quest_parameters, message_type, string_id)
             }
-            _ => {
-                warn!("Unimplemented quest reward: {:?}", reward);
-                false
-            } /*
-              QsdReward::TriggerAfterDelayForObject(_, _, _) => todo!(),
-              QsdReward::FormatAnnounceMessage(_, _) => todo!(),
-              QsdReward::TriggerForZoneTeam(_, _, _) => todo!(),
-              QsdReward::SetRevivePosition(_) => todo!(),
-
-
-              // TODO: Implement clans
-              QsdReward::ClanLevel(_, _) => todo!(),
-              QsdReward::ClanMoney(_, _) => todo!(),
-              QsdReward::ClanPoints(_, _) => todo!(),
-              QsdReward::AddClanSkill(_) => todo!(),
-              QsdReward::RemoveClanSkill(_) => todo!(),
-              QsdReward::ClanPointContribution(_, _) => todo!(),
-              QsdReward::TeleportNearbyClanMembers(_, _, _) => todo!(),
-              */
+            QsdReward::TriggerAfterDelayForObject(object_type, delay, ref name) => {
+                quest_reward_trigger_after_delay_for_object(
+                    quest_world,
+                    quest_parameters,
+                    object_type,
+                    delay,
+                    name,
+                )
+            }
+            QsdReward::TriggerForZoneTeam(zone_id, team_number, ref name) => {
+                quest_reward_trigger_for_zone_team(quest_world, zone_id, team_number, name)
+            }
+            QsdReward::FormatAnnounceMessage(string_id, ref npc_variables) => {
+                quest_reward_format_announce_message(
+                    quest_world,
+                    quest_parameters,
+                    string_id,
+                    npc_variables,
+                )
+            }
+            QsdReward::SetRevivePosition(ref position) => {
+                quest_reward_set_revive_position(quest_parameters, position)
+            }
+            // Rejected above by is_quest_reward_implemented
+            _ => quest_trigger_unimplemented(quest_trigger, reward),
         };
 
         if !result {
@@ -1644,9 +2008,44 @@ fn quest_trigger_apply_rewards(
     true
 }
 
+// Conditions which quest_trigger_check_conditions does not handle, also used by dump-qsd
+pub fn is_quest_condition_implemented(condition: &QsdCondition) -> bool {
+    !matches!(
+        condition,
+        // TODO: Implement party system
+        QsdCondition::Party(_)
+            | QsdCondition::PartyMemberCount(_)
+            // TODO: Implement clan system
+            | QsdCondition::InClan(_)
+            | QsdCondition::ClanPosition(_, _)
+            | QsdCondition::ClanPointContribution(_, _)
+            | QsdCondition::ClanLevel(_, _)
+            | QsdCondition::ClanPoints(_, _)
+            | QsdCondition::ClanMoney(_, _)
+            | QsdCondition::ClanMemberCount(_, _)
+            | QsdCondition::HasClanSkill(_, _)
+    )
+}
+
+// Rewards which quest_trigger_apply_rewards does not handle, also used by dump-qsd
+pub fn is_quest_reward_implemented(reward: &QsdReward) -> bool {
+    !matches!(
+        reward,
+        // TODO: Implement clan system
+        QsdReward::ClanLevel(_, _)
+            | QsdReward::ClanMoney(_, _)
+            | QsdReward::ClanPoints(_, _)
+            | QsdReward::AddClanSkill(_)
+            | QsdReward::RemoveClanSkill(_)
+            | QsdReward::ClanPointContribution(_, _)
+            | QsdReward::TeleportNearbyClanMembers(_, _, _)
+    )
+}
+
 pub fn quest_system(
     mut commands: Commands,
     mut query: Query<(
+        Entity,
         &ClientEntity,
         &AbilityValues,
         &Level,
@@ -1674,37 +2073,80 @@ pub fn quest_system(
     mut object_variables_query: Query<(&mut ObjectVariables, &Position)>,
     mut client_entity_list: ResMut<ClientEntityList>,
     game_data: Res<GameData>,
+    script_engine: Res<ScriptEngine>,
     world_rates: Res<WorldRates>,
     mut server_messages: ResMut<ServerMessages>,
     server_time: Res<ServerTime>,
     world_time: Res<WorldTime>,
+    mut world_variables: ResMut<WorldVariables>,
     mut zone_list: ResMut<ZoneList>,
+    mut pending_quest_trigger_list: ResMut<PendingQuestTriggerList>,
     mut quest_trigger_events: EventReader<QuestTriggerEvent>,
     mut reward_xp_events: EventWriter<RewardXpEvent>,
 ) {
+    // Collect triggers whose delay has expired, followed by triggers requested this frame
+    let mut triggers = Vec::new();
+    for pending_trigger in pending_quest_trigger_list.take_expired(server_time.now) {
+        match pending_trigger.target {
+            PendingQuestTriggerTarget::Entity {
+                trigger_entity,
+                selected_npc,
+                selected_event_object,
+            } => triggers.push((
+                trigger_entity,
+                pending_trigger.trigger_hash,
+                selected_npc,
+                selected_event_object,
+            )),
+            PendingQuestTriggerTarget::ZoneTeam {
+                zone_id,
+                team_number,
+            } => {
+                for (entity, _, _, _, _, position, _, _, (team, ..), game_client) in
+                    query.iter_mut()
+                {
+                    if game_client.is_some()
+                        && position.zone_id == zone_id
+                        && team.id == team_number
+                    {
+                        triggers.push((entity, pending_trigger.trigger_hash, None, None));
+                    }
+                }
+            }
+        }
+    }
+
+    for event in quest_trigger_events.iter() {
+        triggers.push((event.trigger_entity, event.trigger_hash, None, None));
+    }
+
     let mut quest_world = QuestWorld {
         commands: &mut commands,
         client_entity_list: &mut client_entity_list,
         game_data: &game_data,
+        script_engine: &script_engine,
         server_messages: &mut server_messages,
         server_time: &server_time,
         world_rates: &world_rates,
         world_time: &world_time,
+        world_variables: &mut world_variables,
         zone_list: &mut zone_list,
+        pending_quest_trigger_list: &mut pending_quest_trigger_list,
         reward_xp_events: &mut reward_xp_events,
         object_variables_query: &mut object_variables_query,
         rng: rand::thread_rng(),
     };
 
-    for &QuestTriggerEvent {
-        trigger_entity,
-        trigger_hash,
-    } in quest_trigger_events.iter()
-    {
+    for (trigger_entity, trigger_hash, selected_npc, selected_event_object) in triggers {
         let mut trigger = game_data.quests.get_trigger_by_hash(trigger_hash);
         let mut success = false;
 
+        if trigger.is_none() {
+            warn!("Quest trigger {:?} does not exist", trigger_hash);
+        }
+
         if let Ok((
+            _,
             client_entity,
             ability_values,
             level,
@@ -1755,8 +2197,8 @@ pub fn quest_system(
                     team: &mut team,
                     union_membership: union_membership.as_mut(),
                 },
-                selected_event_object: None,
-                selected_npc: None,
+                selected_event_object,
+                selected_npc,
                 selected_quest_index: None,
                 next_trigger_name: None,
             };
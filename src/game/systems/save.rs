@@ -1,4 +1,4 @@
-use bevy_ecs::prelude::{Commands, EventReader, Query, ResMut};
+use bevy_ecs::prelude::{Commands, EventReader, Query, Res, ResMut};
 use log::{error, info};
 
 use crate::{
@@ -6,12 +6,12 @@ use crate::{
     game::{
         bundles::client_entity_leave_zone,
         components::{
-            BasicStats, CharacterInfo, ClientEntity, Equipment, ExperiencePoints, HealthPoints,
-            Hotbar, Inventory, Level, ManaPoints, Position, QuestState, SkillList, SkillPoints,
-            Stamina, StatPoints, UnionMembership,
+            BasicStats, CharacterInfo, ClientEntity, Cooldowns, Equipment, ExperiencePoints,
+            FriendList, HealthPoints, Hotbar, Inventory, Level, Mailbox, ManaPoints, Position,
+            QuestState, SkillList, SkillPoints, Stamina, StatPoints, UnionMembership,
         },
         events::{SaveEvent, SaveEventCharacter},
-        resources::ClientEntityList,
+        resources::{ClientEntityList, ServerTime},
     },
 };
 
@@ -32,10 +32,18 @@ pub fn save_system(
         &ManaPoints,
         &SkillPoints,
         &StatPoints,
-        (&QuestState, &UnionMembership, &Stamina),
+        (
+            &QuestState,
+            &UnionMembership,
+            &Stamina,
+            &FriendList,
+            &Mailbox,
+            &Cooldowns,
+        ),
     )>,
     mut client_entity_list: ResMut<ClientEntityList>,
     mut save_events: EventReader<SaveEvent>,
+    server_time: Res<ServerTime>,
 ) {
     for pending_save in save_events.iter() {
         match *pending_save {
@@ -58,7 +66,7 @@ pub fn save_system(
                     mana_points,
                     skill_points,
                     stat_points,
-                    (quest_state, union_membership, stamina),
+                    (quest_state, union_membership, stamina, friend_list, mailbox, cooldowns),
                 )) = query.get(entity)
                 {
                     let storage = CharacterStorage {
@@ -79,6 +87,9 @@ pub fn save_system(
                         quest_state: quest_state.clone(),
                         union_membership: union_membership.clone(),
                         stamina: *stamina,
+                        friend_list: friend_list.clone(),
+                        mailbox: mailbox.clone(),
+                        cooldowns: cooldowns.to_saved(server_time.now),
                     };
 
                     match storage.save() {
@@ -10,13 +10,16 @@ use crate::{
         StatusEffectType,
     },
     game::{
-        bundles::{ability_values_get_value, MonsterBundle},
+        bundles::{ability_values_get_value, client_entity_teleport_zone, MonsterBundle},
         components::{
-            AbilityValues, BasicStats, CharacterInfo, ClientEntity, ClientEntityType, Equipment,
-            GameClient, HealthPoints, Inventory, Level, MoveSpeed, Npc, Position, SkillList,
-            SpawnOrigin, StatusEffects, Team,
+            AbilityValues, ActiveStatusEffect, BasicStats, CharacterInfo, ClientEntity,
+            ClientEntityType, DeathXpPenalty, Equipment, GameClient, HealthPoints, Inventory,
+            Level, ManaPoints, MoveSpeed, Npc, Position, SkillList, SpawnOrigin, StatusEffects,
+            Team, STATUS_EFFECT_TICK_DURATION,
+        },
+        events::{
+            DamageEvent, RewardXpEvent, SkillCastInterruptedEvent, SkillEvent, SkillEventTarget,
         },
-        events::{DamageEvent, SkillEvent, SkillEventTarget},
         messages::server::{ApplySkillEffect, CancelCastingSkillReason, ServerMessage, UseItem},
         resources::{ClientEntityList, ServerMessages, ServerTime},
         GameData,
@@ -288,10 +291,8 @@ fn apply_skill_status_effects_to_entity(
                     adjust_value,
                 );
 
+                // Fainting and Sleep are enforced by command_system
                 match status_effect_data.status_effect_type {
-                    StatusEffectType::Fainting | StatusEffectType::Sleep => {
-                        // TODO: Set current + next command to stop
-                    }
                     StatusEffectType::Taunt => {
                         // TODO: Set current + next command to attack spell cast entity
                     }
@@ -503,10 +504,20 @@ pub fn skill_effect_system(
         Option<&SkillList>,
         Option<&Npc>,
     )>,
+    resurrection_target_query: Query<(
+        &ClientEntity,
+        &Position,
+        &AbilityValues,
+        &HealthPoints,
+        Option<&DeathXpPenalty>,
+        Option<&GameClient>,
+    )>,
     game_data: Res<GameData>,
     mut client_entity_list: ResMut<ClientEntityList>,
     mut skill_events: EventReader<SkillEvent>,
+    mut skill_cast_interrupted_events: EventReader<SkillCastInterruptedEvent>,
     mut damage_events: EventWriter<DamageEvent>,
+    mut reward_xp_events: EventWriter<RewardXpEvent>,
     mut pending_skill_events: Local<Vec<SkillEvent>>,
     mut server_messages: ResMut<ServerMessages>,
     server_time: Res<ServerTime>,
@@ -527,6 +538,14 @@ pub fn skill_effect_system(
         pending_skill_events.push(skill_event.clone());
     }
 
+    // Casts interrupted by hit stun, crowd control or death never apply their effect
+    for interrupted in skill_cast_interrupted_events.iter() {
+        pending_skill_events.retain(|skill_event| {
+            skill_event.caster_entity != interrupted.caster_entity
+                || skill_event.cast_id != interrupted.cast_id
+        });
+    }
+
     // TODO: drain_filter pls
     let mut i = 0;
     while i != pending_skill_events.len() {
@@ -661,7 +680,7 @@ pub fn skill_effect_system(
                     }
                     SkillType::SummonPet => {
                         if let Some(npc_id) = skill_data.summon_npc_id {
-                            if MonsterBundle::spawn(
+                            if let Some(summon_entity) = MonsterBundle::spawn(
                                 &mut commands,
                                 &mut client_entity_list,
                                 skill_world.game_data,
@@ -675,11 +694,44 @@ pub fn skill_effect_system(
                                 skill_caster.team.clone(),
                                 Some((skill_caster.entity, skill_caster.level)),
                                 Some(skill_data.level as i32),
-                            )
-                            .is_some()
-                            {
+                            ) {
                                 // TODO: Increase summon count point thing
-                                // TODO: Apply status effect to decrease life over time
+
+                                // Summons lose life over time until they die, at the rate of the
+                                // decrease life time status effect
+                                if let Some(decrease_life_per_tick) = skill_world
+                                    .game_data
+                                    .status_effects
+                                    .find_status_effect_by_type(StatusEffectType::DecreaseLifeTime)
+                                    .map(|status_effect_data| {
+                                        status_effect_data.apply_per_second_value
+                                    })
+                                    .filter(|decrease_life_per_tick| *decrease_life_per_tick > 0)
+                                {
+                                    let max_health = skill_world
+                                        .game_data
+                                        .ability_value_calculator
+                                        .calculate_npc(
+                                            npc_id,
+                                            &StatusEffects::new(),
+                                            Some(skill_caster.level.level as i32),
+                                            Some(skill_data.level as i32),
+                                        )
+                                        .map_or(0, |ability_values| {
+                                            ability_values.get_max_health()
+                                        });
+                                    let num_ticks = (max_health + decrease_life_per_tick - 1)
+                                        / decrease_life_per_tick;
+
+                                    let mut status_effects = StatusEffects::new();
+                                    status_effects.active[StatusEffectType::DecreaseLifeTime] =
+                                        Some(ActiveStatusEffect::new(
+                                            decrease_life_per_tick,
+                                            skill_world.server_time.now
+                                                + STATUS_EFFECT_TICK_DURATION * num_ticks as u32,
+                                        ));
+                                    commands.entity(summon_entity).insert(status_effects);
+                                }
                                 Ok(())
                             } else {
                                 Err(SkillCastError::InvalidSkill)
@@ -694,7 +746,55 @@ pub fn skill_effect_system(
                     | SkillType::Emote
                     | SkillType::Warp => Ok(()),
                     SkillType::Resurrection => {
-                        warn!("Unimplemented skill type used {:?}", skill_data);
+                        if let SkillEventTarget::Entity(target_entity) = skill_target {
+                            if let Ok((
+                                target_client_entity,
+                                target_position,
+                                target_ability_values,
+                                target_health_points,
+                                target_death_xp_penalty,
+                                target_game_client,
+                            )) = resurrection_target_query.get(target_entity)
+                            {
+                                if target_health_points.hp == 0 {
+                                    // Restore a percentage of the xp lost on death
+                                    if let Some(death_xp_penalty) = target_death_xp_penalty {
+                                        let restore_xp = death_xp_penalty.xp
+                                            * skill_data.power.min(100) as u64
+                                            / 100;
+                                        if restore_xp > 0 {
+                                            reward_xp_events.send(RewardXpEvent::new(
+                                                target_entity,
+                                                restore_xp,
+                                                0,
+                                                None,
+                                            ));
+                                        }
+                                    }
+
+                                    // Revive the target where they died, same as a revive request
+                                    commands
+                                        .entity(target_entity)
+                                        .remove::<DeathXpPenalty>()
+                                        .insert(HealthPoints::new(
+                                            target_ability_values.get_max_health() as u32,
+                                        ))
+                                        .insert(ManaPoints::new(
+                                            target_ability_values.get_max_mana() as u32,
+                                        ));
+                                    client_entity_teleport_zone(
+                                        &mut commands,
+                                        &mut client_entity_list,
+                                        target_entity,
+                                        target_client_entity,
+                                        target_position,
+                                        target_position.clone(),
+                                        target_game_client,
+                                    );
+                                }
+                            }
+                        }
+
                         Ok(())
                     }
                 };
@@ -1,9 +1,13 @@
-use bevy_ecs::prelude::{Query, Res, ResMut};
+use bevy_ecs::prelude::{Entity, EventWriter, Query, Res, ResMut};
 
 use crate::{
-    data::StatusEffectType,
+    data::{Damage, StatusEffectType},
     game::{
-        components::{AbilityValues, ClientEntity, HealthPoints, ManaPoints, StatusEffects},
+        components::{
+            AbilityValues, ClientEntity, HealthPoints, ManaPoints, StatusEffects,
+            STATUS_EFFECT_TICK_DURATION,
+        },
+        events::DamageEvent,
         messages::server::{ServerMessage, UpdateStatusEffects},
         resources::{ServerMessages, ServerTime},
     },
@@ -11,23 +15,43 @@ use crate::{
 
 pub fn status_effect_system(
     mut query: Query<(
+        Entity,
         &ClientEntity,
         &AbilityValues,
         &mut HealthPoints,
         &mut ManaPoints,
         &mut StatusEffects,
     )>,
+    mut damage_events: EventWriter<DamageEvent>,
     mut server_messages: ResMut<ServerMessages>,
     server_time: Res<ServerTime>,
 ) {
-    for (client_entity, ability_values, mut health_points, mut mana_points, mut status_effects) in
-        query.iter_mut()
+    for (
+        entity,
+        client_entity,
+        ability_values,
+        mut health_points,
+        mut mana_points,
+        mut status_effects,
+    ) in query.iter_mut()
     {
+        // Per tick status effects do not apply to dead entities
+        let is_dead = health_points.hp == 0;
         let mut status_effects_expired = false;
+        let mut status_effects_tick = false;
+        let mut tick_hp = false;
+        let mut tick_mp = false;
 
-        for (_, status_effect_slot) in status_effects.active.iter() {
+        for (status_effect_type, status_effect_slot) in status_effects.active.iter() {
             if let Some(status_effect) = status_effect_slot {
-                // TODO: Process per tick status effect: IncreaseHp, IncreaseMp, Poisoned, DecreaseLifeTime
+                if !is_dead
+                    && status_effect_type.is_per_tick()
+                    && status_effect
+                        .next_tick_time
+                        .map_or(true, |next_tick_time| next_tick_time <= server_time.now)
+                {
+                    status_effects_tick = true;
+                }
 
                 if status_effect.expire_time <= server_time.now {
                     status_effects_expired = true;
@@ -35,10 +59,76 @@ pub fn status_effect_system(
             }
         }
 
+        if status_effects_tick {
+            // Process per tick status effects
+            let max_hp = ability_values.get_max_health() as u32;
+            let max_mp = ability_values.get_max_mana() as u32;
+            let mut tick_damage = 0;
+
+            for (status_effect_type, status_effect_slot) in status_effects.active.iter_mut() {
+                if let Some(status_effect) = status_effect_slot {
+                    if !status_effect_type.is_per_tick() {
+                        continue;
+                    }
+
+                    let next_tick_time = status_effect
+                        .next_tick_time
+                        .get_or_insert(server_time.now + STATUS_EFFECT_TICK_DURATION);
+
+                    while *next_tick_time <= server_time.now
+                        && *next_tick_time <= status_effect.expire_time
+                    {
+                        *next_tick_time += STATUS_EFFECT_TICK_DURATION;
+
+                        let value = status_effect.value.max(0) as u32;
+                        match status_effect_type {
+                            StatusEffectType::IncreaseHp => {
+                                health_points.hp =
+                                    health_points.hp.saturating_add(value).min(max_hp);
+                                tick_hp = true;
+                            }
+                            StatusEffectType::IncreaseMp => {
+                                mana_points.mp = mana_points.mp.saturating_add(value).min(max_mp);
+                                tick_mp = true;
+                            }
+                            StatusEffectType::Poisoned | StatusEffectType::DecreaseLifeTime => {
+                                tick_damage += value;
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            if tick_damage > 0 {
+                // Damage goes through damage_system so death is handled as normal
+                damage_events.send(DamageEvent::with_status_effect(
+                    entity,
+                    Damage {
+                        amount: tick_damage,
+                        is_critical: false,
+                        apply_hit_stun: false,
+                    },
+                ));
+            }
+
+            if (tick_hp || tick_mp) && !status_effects_expired {
+                server_messages.send_entity_message(
+                    client_entity,
+                    ServerMessage::UpdateStatusEffects(UpdateStatusEffects {
+                        entity_id: client_entity.id,
+                        status_effects: status_effects.clone(),
+                        updated_hp: if tick_hp { Some(*health_points) } else { None },
+                        updated_mp: if tick_mp { Some(*mana_points) } else { None },
+                    }),
+                );
+            }
+        }
+
         if status_effects_expired {
-            // Remove expired status effects
-            let mut cleared_hp = false;
-            let mut cleared_mp = false;
+            // Remove expired status effects, the expiry message also includes any tick update
+            let mut cleared_hp = tick_hp;
+            let mut cleared_mp = tick_mp;
 
             for (status_effect_type, status_effect_slot) in status_effects.active.iter_mut() {
                 if let Some(status_effect) = status_effect_slot {
@@ -64,7 +154,7 @@ pub fn status_effect_system(
 
             // Immediately adjust hp / mp for the update packet
             let max_hp = ability_values.get_max_health() as u32;
-            let max_mp = ability_values.get_max_health() as u32;
+            let max_mp = ability_values.get_max_mana() as u32;
 
             if health_points.hp > max_hp {
                 health_points.hp = max_hp;
@@ -7,23 +7,29 @@ use crate::{
         AbilityType, SkillType,
     },
     game::{
-        bundles::{ability_values_add_value, ability_values_get_value, skill_list_try_learn_skill},
+        bundles::{
+            ability_values_add_value, ability_values_get_value, client_entity_teleport_zone,
+            skill_list_try_learn_skill, SkillListLearnRequirements,
+        },
         components::{
-            AbilityValues, BasicStats, CharacterInfo, ClientEntity, ExperiencePoints, GameClient,
-            Inventory, ItemSlot, Level, MoveSpeed, NextCommand, SkillList, SkillPoints, Stamina,
-            StatPoints, Team, UnionMembership,
+            AbilityValues, BasicStats, CharacterInfo, ClientEntity, CooldownType, Cooldowns,
+            ExperiencePoints, GameClient, Inventory, ItemSlot, Level, MoveSpeed, NextCommand,
+            Position, SkillList, SkillPoints, Stamina, StatPoints, StatusEffects, Team,
+            UnionMembership, STATUS_EFFECT_TICK_DURATION,
         },
         events::UseItemEvent,
-        messages::server::{ServerMessage, UseItem},
-        resources::ServerMessages,
+        messages::server::{ServerMessage, UseItem, Whisper},
+        resources::{ClientEntityList, ServerMessages, ServerTime},
         GameData,
     },
 };
 
-struct UseItemWorld<'a, 'b, 'c, 'd, 'e> {
+struct UseItemWorld<'a, 'b, 'c, 'd, 'e, 'f, 'g> {
     pub commands: &'a mut Commands<'b>,
+    pub client_entity_list: &'f mut ResMut<'g, ClientEntityList>,
     pub game_data: &'c GameData,
     pub server_messages: &'d mut ResMut<'e, ServerMessages>,
+    pub server_time: &'c ServerTime,
 }
 
 struct UseItemUser<'a, 'world> {
@@ -32,15 +38,18 @@ struct UseItemUser<'a, 'world> {
     pub basic_stats: &'a mut Mut<'world, BasicStats>,
     pub character_info: &'a CharacterInfo,
     pub client_entity: &'a ClientEntity,
+    pub cooldowns: &'a mut Mut<'world, Cooldowns>,
     pub experience_points: &'a ExperiencePoints,
     pub game_client: Option<&'a GameClient>,
     pub inventory: &'a mut Mut<'world, Inventory>,
     pub level: &'a Level,
     pub move_speed: &'a MoveSpeed,
+    pub position: &'a Position,
     pub skill_list: &'a mut Mut<'world, SkillList>,
     pub skill_points: &'a mut Mut<'world, SkillPoints>,
     pub stamina: &'a mut Mut<'world, Stamina>,
     pub stat_points: &'a mut Mut<'world, StatPoints>,
+    pub status_effects: &'a mut Mut<'world, StatusEffects>,
     pub team_number: &'a Team,
     pub union_membership: &'a mut Mut<'world, UnionMembership>,
 }
@@ -48,6 +57,7 @@ struct UseItemUser<'a, 'world> {
 enum UseItemError {
     InvalidItem,
     AbilityRequirement,
+    Cooldown,
 }
 
 fn use_inventory_item(
@@ -72,7 +82,25 @@ fn use_inventory_item(
         .get_consumable_item(item.get_item_number())
         .ok_or(UseItemError::InvalidItem)?;
 
-    // TODO: Check use item cooldown
+    let cooldown_type = CooldownType::ConsumableItem(item_data.cooldown_type_id);
+    if let Some(cooldown_remaining) = use_item_user
+        .cooldowns
+        .get_remaining(cooldown_type, use_item_world.server_time.now)
+    {
+        if let Some(game_client) = use_item_user.game_client {
+            game_client
+                .server_message_tx
+                .send(ServerMessage::Whisper(Whisper {
+                    from: String::from("SERVER"),
+                    text: format!(
+                        "You must wait {} seconds before using this item again",
+                        cooldown_remaining.as_secs() + 1
+                    ),
+                }))
+                .ok();
+        }
+        return Err(UseItemError::Cooldown);
+    }
 
     if let Some((require_ability_type, require_ability_value)) = item_data.ability_requirement {
         let ability_value = ability_values_get_value(
@@ -108,6 +136,7 @@ fn use_inventory_item(
 
     let (consume_item, message_to_nearby) = match item_data.item_data.class {
         ItemClass::MagicItem => {
+            let mut return_warp = false;
             if let Some(skill_id) = item_data.use_skill_id {
                 if let Some(skill_data) = use_item_world.game_data.skills.get_skill(skill_id) {
                     if skill_data.skill_type.is_self_skill() {
@@ -126,22 +155,59 @@ fn use_inventory_item(
                             ),
                         );
                     } else if matches!(skill_data.skill_type, SkillType::Warp) {
-                        // TODO: Handle warp immediately rather than casting skill
-                        warn!("Unimplemented use of Warp item with item {:?}", item);
+                        if skill_data.warp_zone_id.is_none() {
+                            // Warp items without a destination return to the save point
+                            let revive_zone_id = use_item_user.character_info.revive_zone_id;
+                            if use_item_world
+                                .client_entity_list
+                                .get_zone(revive_zone_id)
+                                .is_some()
+                            {
+                                client_entity_teleport_zone(
+                                    use_item_world.commands,
+                                    use_item_world.client_entity_list,
+                                    use_item_user.entity,
+                                    use_item_user.client_entity,
+                                    use_item_user.position,
+                                    Position::new(
+                                        use_item_user.character_info.revive_position,
+                                        revive_zone_id,
+                                    ),
+                                    use_item_user.game_client,
+                                );
+                                return_warp = true;
+                            }
+                        } else {
+                            // TODO: Handle warp immediately rather than casting skill
+                            warn!("Unimplemented use of Warp item with item {:?}", item);
+                        }
                     }
                 }
             }
 
-            (false, false)
+            (return_warp, false)
         }
         ItemClass::SkillBook => {
             if let Some(skill_id) = item_data.learn_skill_id {
                 (
                     skill_list_try_learn_skill(
-                        use_item_world.game_data.skills.as_ref(),
+                        use_item_world.game_data,
                         skill_id,
                         use_item_user.skill_list,
                         Some(use_item_user.skill_points),
+                        &SkillListLearnRequirements {
+                            ability_values: use_item_user.ability_values,
+                            level: use_item_user.level,
+                            move_speed: use_item_user.move_speed,
+                            position: use_item_user.position,
+                            team: use_item_user.team_number,
+                            character_info: Some(use_item_user.character_info),
+                            experience_points: Some(use_item_user.experience_points),
+                            inventory: Some(use_item_user.inventory),
+                            stamina: Some(use_item_user.stamina),
+                            stat_points: Some(use_item_user.stat_points),
+                            union_membership: Some(use_item_user.union_membership),
+                        },
                         use_item_user.game_client,
                     )
                     .is_ok(),
@@ -159,11 +225,39 @@ fn use_inventory_item(
             (false, false)
         }
         _ => {
-            if let Some(_apply_status_effect_id) = item_data.apply_status_effect_id {
-                // TODO: Implement status effects
-            }
+            let status_effect_data = item_data
+                .apply_status_effect_id
+                .and_then(|status_effect_id| {
+                    use_item_world
+                        .game_data
+                        .status_effects
+                        .get_status_effect(status_effect_id)
+                })
+                .filter(|status_effect_data| status_effect_data.apply_per_second_value > 0);
+
+            if let (Some(status_effect_data), Some((_, add_ability_value))) =
+                (status_effect_data, item_data.add_ability)
+            {
+                // Potions apply their add ability value over time with a per tick status effect
+                let per_tick_value = status_effect_data.apply_per_second_value;
+                if !use_item_user
+                    .status_effects
+                    .can_apply(status_effect_data, per_tick_value)
+                {
+                    use_item_user
+                        .inventory
+                        .try_stack_with_item(item_slot, item)
+                        .expect("Unexpected error returning unconsumed item to inventory");
+                    return Ok(());
+                }
 
-            if let Some((add_ability_type, add_ability_value)) = item_data.add_ability {
+                let num_ticks = (add_ability_value.max(0) + per_tick_value - 1) / per_tick_value;
+                use_item_user.status_effects.apply_status_effect(
+                    status_effect_data,
+                    use_item_world.server_time.now + STATUS_EFFECT_TICK_DURATION * num_ticks as u32,
+                    per_tick_value,
+                );
+            } else if let Some((add_ability_type, add_ability_value)) = item_data.add_ability {
                 ability_values_add_value(
                     add_ability_type,
                     add_ability_value,
@@ -182,6 +276,12 @@ fn use_inventory_item(
     };
 
     if consume_item {
+        use_item_user.cooldowns.set_cooldown(
+            cooldown_type,
+            use_item_world.server_time.now,
+            item_data.cooldown_duration,
+        );
+
         if let Some(game_client) = use_item_user.game_client {
             if message_to_nearby {
                 use_item_world.server_messages.send_entity_message(
@@ -239,26 +339,33 @@ pub fn use_item_system(
         &ExperiencePoints,
         &Level,
         &MoveSpeed,
+        &Position,
         &Team,
         (
             &mut BasicStats,
+            &mut Cooldowns,
             &mut Inventory,
             &mut SkillList,
             &mut SkillPoints,
             &mut Stamina,
             &mut StatPoints,
+            &mut StatusEffects,
             &mut UnionMembership,
         ),
         Option<&GameClient>,
     )>,
+    mut client_entity_list: ResMut<ClientEntityList>,
     game_data: Res<GameData>,
     mut use_item_events: EventReader<UseItemEvent>,
     mut server_messages: ResMut<ServerMessages>,
+    server_time: Res<ServerTime>,
 ) {
     let mut use_item_world = UseItemWorld {
         commands: &mut commands,
+        client_entity_list: &mut client_entity_list,
         game_data: &game_data,
         server_messages: &mut server_messages,
+        server_time: &server_time,
     };
 
     for &UseItemEvent {
@@ -274,14 +381,17 @@ pub fn use_item_system(
             experience_points,
             level,
             move_speed,
+            position,
             team_number,
             (
                 mut basic_stats,
+                mut cooldowns,
                 mut inventory,
                 mut skill_list,
                 mut skill_points,
                 mut stamina,
                 mut stat_points,
+                mut status_effects,
                 mut union_membership,
             ),
             game_client,
@@ -293,14 +403,17 @@ pub fn use_item_system(
                 basic_stats: &mut basic_stats,
                 character_info,
                 client_entity,
+                cooldowns: &mut cooldowns,
                 experience_points,
                 inventory: &mut inventory,
                 level,
                 move_speed,
+                position,
                 skill_list: &mut skill_list,
                 skill_points: &mut skill_points,
                 stamina: &mut stamina,
                 stat_points: &mut stat_points,
+                status_effects: &mut status_effects,
                 team_number,
                 union_membership: &mut union_membership,
                 game_client,
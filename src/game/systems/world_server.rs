@@ -5,6 +5,7 @@ use crate::{
     data::{
         account::{AccountStorage, AccountStorageError},
         character::CharacterStorage,
+        mail::MailStorage,
     },
     game::{
         components::{Account, CharacterDeleteTime, CharacterList, ServerInfo, WorldClient},
@@ -49,6 +50,7 @@ pub fn world_server_authentication_system(
                                                 {
                                                     CharacterStorage::delete(&character.info.name)
                                                         .ok();
+                                                    MailStorage::delete(&character.info.name).ok();
                                                     false
                                                 } else {
                                                     character_list.characters.push(character);
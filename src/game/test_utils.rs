@@ -0,0 +1,487 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use arrayvec::ArrayVec;
+use chrono::Local;
+use nalgebra::{Point2, Point3};
+
+use crate::{
+    data::{
+        character::CharacterStorage,
+        item::{Item, ItemClass},
+        AbilityValueCalculator, AiDatabase, BaseItemData, CharacterCreator, CharacterCreatorError,
+        Damage, DropTable, ItemDatabase, ItemReference, JobClassDatabase, MaterialItemData,
+        MotionDatabase, NpcData, NpcDatabase, NpcId, PassiveRecoveryState, QuestDatabase,
+        SkillActionMode, SkillAddAbility, SkillCooldown, SkillData, SkillDatabase, SkillId,
+        SkillPageType, SkillTargetFilter, SkillType, StatusEffectDatabase, ZoneData, ZoneDatabase,
+        ZoneId,
+    },
+    game::{
+        components::{
+            AbilityValues, BasicStatType, BasicStats, CharacterInfo, DamageCategory, DamageType,
+            DroppedItem, Equipment, Level, SkillList, StatusEffects,
+        },
+        resources::ServerTime,
+        GameData,
+    },
+};
+
+pub const TEST_ZONE_ID: u16 = 1;
+pub const TEST_ZONE_SECTOR_SIZE: u32 = 10000;
+pub const TEST_ZONE_SECTOR_COUNT: u32 = 8;
+
+// Simple deterministic formulas so tests can calculate the expected results by hand
+pub struct TestAbilityValueCalculator;
+
+impl TestAbilityValueCalculator {
+    pub fn levelup_require_xp(level: u32) -> u64 {
+        level as u64 * 100
+    }
+}
+
+impl AbilityValueCalculator for TestAbilityValueCalculator {
+    fn calculate(
+        &self,
+        _character_info: &CharacterInfo,
+        level: &Level,
+        _equipment: &Equipment,
+        _basic_stats: &BasicStats,
+        _skill_list: &SkillList,
+        status_effects: &StatusEffects,
+    ) -> AbilityValues {
+        let mut ability_values = test_ability_values();
+        ability_values.level = level.level as i32;
+        ability_values.max_health = 100 * level.level as i32;
+        ability_values.max_mana = 50 * level.level as i32;
+        ability_values.adjust = status_effects.into();
+        ability_values
+    }
+
+    fn calculate_npc(
+        &self,
+        _npc_id: NpcId,
+        status_effects: &StatusEffects,
+        _owner_level: Option<i32>,
+        _summon_skill_level: Option<i32>,
+    ) -> Option<AbilityValues> {
+        let mut ability_values = test_ability_values();
+        ability_values.damage_category = DamageCategory::Npc;
+        ability_values.adjust = status_effects.into();
+        Some(ability_values)
+    }
+
+    fn calculate_damage(
+        &self,
+        _attacker: &AbilityValues,
+        _defender: &AbilityValues,
+        _hit_count: i32,
+    ) -> Damage {
+        Damage {
+            amount: 1,
+            is_critical: false,
+            apply_hit_stun: false,
+        }
+    }
+
+    fn calculate_skill_adjust_value(
+        &self,
+        _skill_add_ability: &SkillAddAbility,
+        _caster_intelligence: i32,
+        ability_value: i32,
+    ) -> i32 {
+        ability_value
+    }
+
+    fn calculate_skill_damage(
+        &self,
+        _attacker: &AbilityValues,
+        _defender: &AbilityValues,
+        _skill_data: &SkillData,
+        _hit_count: i32,
+    ) -> Damage {
+        Damage {
+            amount: 1,
+            is_critical: false,
+            apply_hit_stun: false,
+        }
+    }
+
+    fn calculate_give_xp(
+        &self,
+        _attacker_level: i32,
+        _attacker_damage: i32,
+        _defender_level: i32,
+        _defender_max_hp: i32,
+        defender_reward_xp: i32,
+        _world_xp_rate: i32,
+    ) -> i32 {
+        defender_reward_xp
+    }
+
+    fn calculate_give_stamina(
+        &self,
+        _experience_points: i32,
+        _level: i32,
+        _world_stamina_rate: i32,
+    ) -> i32 {
+        0
+    }
+
+    fn calculate_basic_stat_increase_cost(
+        &self,
+        _basic_stats: &BasicStats,
+        _basic_stat_type: BasicStatType,
+    ) -> Option<u32> {
+        Some(1)
+    }
+
+    fn calculate_levelup_require_xp(&self, level: u32) -> u64 {
+        Self::levelup_require_xp(level)
+    }
+
+    fn calculate_levelup_reward_skill_points(&self, _level: u32) -> u32 {
+        1
+    }
+
+    fn calculate_levelup_reward_stat_points(&self, _level: u32) -> u32 {
+        10
+    }
+
+    fn calculate_reward_value(
+        &self,
+        _equation_id: usize,
+        base_reward_value: i32,
+        _dup_count: i32,
+        _level: i32,
+        _charm: i32,
+        _fame: i32,
+        _world_reward_rate: i32,
+    ) -> i32 {
+        base_reward_value
+    }
+
+    fn calculate_npc_store_item_buy_price(
+        &self,
+        _item: ItemReference,
+        item_data: &BaseItemData,
+        _buy_skill_value: i32,
+        _world_prices_rate: i32,
+    ) -> i32 {
+        item_data.base_price as i32
+    }
+
+    fn calculate_npc_store_item_sell_price(
+        &self,
+        _item: &Item,
+        item_data: &BaseItemData,
+        _sell_skill_value: i32,
+        _world_prices_rate: i32,
+    ) -> i32 {
+        item_data.base_price as i32 / 2
+    }
+
+    fn calculate_passive_recover_hp(
+        &self,
+        _ability_values: &AbilityValues,
+        _recovery_state: PassiveRecoveryState,
+    ) -> i32 {
+        0
+    }
+
+    fn calculate_passive_recover_mp(
+        &self,
+        _ability_values: &AbilityValues,
+        _recovery_state: PassiveRecoveryState,
+    ) -> i32 {
+        0
+    }
+}
+
+struct TestCharacterCreator;
+
+impl CharacterCreator for TestCharacterCreator {
+    fn create(
+        &self,
+        _name: String,
+        _gender: u8,
+        _birth_stone: u8,
+        _face: u8,
+        _hair: u8,
+    ) -> Result<CharacterStorage, CharacterCreatorError> {
+        Err(CharacterCreatorError::InvalidName)
+    }
+
+    fn get_basic_stats(&self, _gender: u8) -> Result<BasicStats, CharacterCreatorError> {
+        Err(CharacterCreatorError::InvalidGender)
+    }
+}
+
+struct TestDropTable;
+
+impl DropTable for TestDropTable {
+    fn get_drop(
+        &self,
+        _world_drop_item_rate: i32,
+        _world_drop_money_rate: i32,
+        _npc_id: NpcId,
+        _zone_id: ZoneId,
+        _level_difference: i32,
+        _character_drop_rate: i32,
+        _character_charm: i32,
+    ) -> Option<DroppedItem> {
+        None
+    }
+}
+
+pub fn test_ability_values() -> AbilityValues {
+    AbilityValues {
+        damage_category: DamageCategory::Character,
+        level: 1,
+        walk_speed: 200.0,
+        run_speed: 400.0,
+        strength: 15,
+        dexterity: 15,
+        intelligence: 15,
+        concentration: 15,
+        charm: 15,
+        sense: 15,
+        max_health: 100,
+        max_mana: 50,
+        additional_health_recovery: 0,
+        additional_mana_recovery: 0,
+        attack_damage_type: DamageType::Physical,
+        attack_power: 10,
+        attack_speed: 100,
+        passive_attack_speed: 0,
+        attack_range: 100,
+        hit: 10,
+        defence: 10,
+        resistance: 10,
+        critical: 10,
+        avoid: 10,
+        max_damage_sources: 0,
+        drop_rate: 0,
+        max_weight: 1000,
+        summon_owner_level: None,
+        summon_skill_level: None,
+        adjust: (&StatusEffects::new()).into(),
+        npc_store_buy_rate: 0,
+        npc_store_sell_rate: 0,
+    }
+}
+
+pub fn test_base_item_data(base_price: u32) -> BaseItemData {
+    BaseItemData {
+        name: String::from("Test Item"),
+        class: ItemClass::Material,
+        base_price,
+        price_rate: 0,
+        weight: 1,
+        quality: 0,
+        icon_index: 0,
+        field_model_index: 0,
+        equip_sound_index: 0,
+        craft_skill_type: 0,
+        craft_skill_level: 0,
+        craft_material: 0,
+        craft_difficulty: 0,
+        equip_class_requirement: 0,
+        equip_union_requirement: ArrayVec::new(),
+        equip_ability_requirement: ArrayVec::new(),
+        add_ability_union_requirement: ArrayVec::new(),
+        add_ability: ArrayVec::new(),
+        durability: 0,
+        rare_type: 0,
+        defence: 0,
+        resistance: 0,
+    }
+}
+
+pub fn test_item_database(materials: Vec<(u16, BaseItemData)>) -> ItemDatabase {
+    ItemDatabase::new(
+        HashMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        HashMap::new(),
+        materials
+            .into_iter()
+            .map(|(id, item_data)| (id, MaterialItemData { item_data }))
+            .collect(),
+        HashMap::new(),
+        HashMap::new(),
+        Vec::new(),
+    )
+}
+
+pub fn test_npc_data(id: NpcId) -> NpcData {
+    NpcData {
+        id,
+        name: String::from("Test NPC"),
+        walk_speed: 200,
+        run_speed: 400,
+        scale: 1.0,
+        right_hand_part_index: 0,
+        left_hand_part_index: 0,
+        level: 1,
+        health_points: 100,
+        attack: 10,
+        hit: 10,
+        defence: 10,
+        resistance: 10,
+        avoid: 10,
+        attack_speed: 100,
+        is_attack_magic_damage: false,
+        ai_file_index: 0,
+        reward_xp: 0,
+        drop_table_index: 0,
+        drop_money_rate: 0,
+        drop_item_rate: 0,
+        npc_minimap_icon_index: 0,
+        summon_point_requirement: 0,
+        store_tabs: [None; 4],
+        store_union_number: None,
+        is_untargetable: false,
+        attack_range: 100,
+        npc_type_index: 0,
+        hit_sound_index: 0,
+        face_icon_index: 0,
+        summon_monster_type: 0,
+        normal_effect_sound_index: 0,
+        attack_sound_index: 0,
+        hitted_sound_index: 0,
+        hand_hit_effect_index: 0,
+        dead_effect_index: 0,
+        die_sound_index: 0,
+        npc_quest_type: 0,
+        glow_colour: (0.0, 0.0, 0.0),
+        create_effect_index: 0,
+        create_sound_index: 0,
+        death_quest_trigger_name: String::new(),
+        npc_height: 0,
+        motion_data: HashMap::new(),
+    }
+}
+
+// A level 1 active skill with no requirements
+pub fn test_skill_data(id: SkillId) -> SkillData {
+    SkillData {
+        id,
+        name: String::from("Test Skill"),
+        base_skill_id: None,
+        level: 1,
+        learn_point_cost: 0,
+        learn_money_cost: 0,
+        skill_type: SkillType::BasicAction,
+        page: SkillPageType::Active,
+        icon_number: 0,
+        use_ability: ArrayVec::new(),
+        required_ability: ArrayVec::new(),
+        required_job_class: None,
+        required_planet: None,
+        required_skills: ArrayVec::new(),
+        required_union: ArrayVec::new(),
+        required_weapon_class: ArrayVec::new(),
+        action_mode: SkillActionMode::Stop,
+        action_motion_id: None,
+        action_motion_speed: 1.0,
+        add_ability: ArrayVec::new(),
+        cast_range: 0,
+        casting_motion_id: None,
+        casting_motion_speed: 1.0,
+        casting_repeat_motion_id: None,
+        casting_repeat_motion_count: 0,
+        cooldown: SkillCooldown::Skill(Duration::from_secs(0)),
+        damage_type: 0,
+        harm: 0,
+        item_make_number: 0,
+        power: 0,
+        scope: 0,
+        status_effects: [None; 2],
+        status_effect_duration: Duration::from_secs(0),
+        success_ratio: 0,
+        summon_npc_id: None,
+        target_filter: SkillTargetFilter::OnlySelf,
+        warp_zone_id: None,
+        warp_zone_x: 0,
+        warp_zone_y: 0,
+    }
+}
+
+pub fn test_zone_id() -> ZoneId {
+    ZoneId::new(TEST_ZONE_ID).unwrap()
+}
+
+// An empty zone made of TEST_ZONE_SECTOR_COUNT x TEST_ZONE_SECTOR_COUNT sectors starting at 0, 0
+pub fn test_zone_data(planet: u32) -> ZoneData {
+    ZoneData {
+        id: test_zone_id(),
+        name: String::from("Test Zone"),
+        sector_size: TEST_ZONE_SECTOR_SIZE,
+        grid_per_patch: 4.0,
+        grid_size: 250.0,
+        event_objects: Vec::new(),
+        monster_spawns: Vec::new(),
+        npcs: Vec::new(),
+        sectors_base_position: Point2::new(0.0, 0.0),
+        num_sectors_x: TEST_ZONE_SECTOR_COUNT,
+        num_sectors_y: TEST_ZONE_SECTOR_COUNT,
+        start_position: Point3::new(0.0, 0.0, 0.0),
+        revive_positions: Vec::new(),
+        day_cycle: 160,
+        morning_time: 0,
+        day_time: 40,
+        evening_time: 80,
+        night_time: 120,
+        planet,
+    }
+}
+
+pub fn test_game_data() -> GameData {
+    let mut zones = HashMap::new();
+    zones.insert(test_zone_id(), test_zone_data(1));
+
+    GameData {
+        character_creator: Box::new(TestCharacterCreator),
+        ability_value_calculator: Box::new(TestAbilityValueCalculator),
+        drop_table: Box::new(TestDropTable),
+        ai: Arc::new(AiDatabase {
+            strings: HashMap::new(),
+            aips: HashMap::new(),
+        }),
+        items: Arc::new(test_item_database(Vec::new())),
+        job_classes: Arc::new(JobClassDatabase::new(HashMap::new())),
+        motions: Arc::new(MotionDatabase::new(0, Vec::new(), Vec::new())),
+        npcs: Arc::new(NpcDatabase::new(
+            HashMap::new(),
+            HashMap::new(),
+            HashMap::new(),
+        )),
+        quests: Arc::new(QuestDatabase {
+            quests: Vec::new(),
+            strings: HashMap::new(),
+            triggers: HashMap::new(),
+            triggers_by_hash: HashMap::new(),
+        }),
+        skills: Arc::new(SkillDatabase::new(HashMap::new())),
+        status_effects: Arc::new(StatusEffectDatabase::new(HashMap::new())),
+        zones: Arc::new(ZoneDatabase::new(zones)),
+    }
+}
+
+pub fn test_server_time() -> ServerTime {
+    ServerTime {
+        delta: Duration::from_millis(33),
+        now: Instant::now(),
+        local_time: Local::now(),
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::Arc;
 
 use crate::{
     data::{
-        item::{ItemClass, ItemType, ItemWeaponType},
+        item::{Item, ItemClass, ItemType, ItemWeaponType},
         AbilityType, AbilityValueCalculator, BaseItemData, Damage, ItemDatabase, ItemReference,
         NpcDatabase, NpcId, PassiveRecoveryState, SkillAddAbility, SkillData, SkillDatabase,
         SkillId,
@@ -643,7 +643,8 @@ impl AbilityValueCalculator for AbilityValuesData {
             114..=150 => (((level - 31) * (level - 20) * (level + 4)) as f64 * 3.8) as u64,
             151..=189 => (((level - 67) * (level - 20) * (level - 10)) as f64 * 6.0) as u64,
             190..=u64::MAX => {
-                ((level - 90) * (level - 120) * (level - 60) * (level - 170) * (level - 188)) as u64
+                let level = level as u64;
+                (level - 90) * (level - 120) * (level - 60) * (level - 170) * (level - 188)
             }
         }
     }
@@ -735,6 +736,54 @@ impl AbilityValueCalculator for AbilityValuesData {
         }
     }
 
+    fn calculate_npc_store_item_sell_price(
+        &self,
+        item: &Item,
+        item_data: &BaseItemData,
+        sell_skill_value: i32,
+        world_prices_rate: i32,
+    ) -> i32 {
+        match item {
+            Item::Equipment(equipment_item) => match equipment_item.item.item_type {
+                ItemType::Face
+                | ItemType::Head
+                | ItemType::Body
+                | ItemType::Hands
+                | ItemType::Feet
+                | ItemType::Back
+                | ItemType::Weapon
+                | ItemType::SubWeapon
+                | ItemType::Vehicle => {
+                    (item_data.base_price as f32
+                        * (40.0 + equipment_item.grade as f32)
+                        * (200.0 + equipment_item.durability as f32)
+                        * (200.0 - world_prices_rate as f32)
+                        * (1.0 + sell_skill_value as f32 * 0.01)
+                        * (1000.0 + equipment_item.life as f32)
+                        / 10000000000.0) as i32
+                }
+                ItemType::Jewellery => {
+                    (item_data.base_price as f32
+                        * 1000.0
+                        * (1.0 + sell_skill_value as f32 * 0.01)
+                        * (200.0 - world_prices_rate as f32)
+                        / 1000000.0) as i32
+                }
+                _ => 0,
+            },
+            Item::Stackable(stackable_item) => match stackable_item.item.item_type {
+                ItemType::Consumable | ItemType::Material | ItemType::Gem | ItemType::Quest => {
+                    (item_data.base_price as f32
+                        * 1000.0
+                        * (1.0 + sell_skill_value as f32 * 0.01)
+                        * (200.0 - world_prices_rate as f32)
+                        / 1000000.0) as i32
+                }
+                _ => 0,
+            },
+        }
+    }
+
     fn calculate_passive_recover_hp(
         &self,
         ability_values: &AbilityValues,
@@ -1830,3 +1879,37 @@ fn calculate_max_weight(
 
     max_weight
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::test_utils::test_game_data;
+
+    fn create_calculator() -> impl AbilityValueCalculator {
+        let game_data = test_game_data();
+        *get_ability_value_calculator(game_data.items, game_data.skills, game_data.npcs).unwrap()
+    }
+
+    #[test]
+    fn levelup_require_xp_formula_boundaries() {
+        let calculator = create_calculator();
+        assert_eq!(calculator.calculate_levelup_require_xp(1), 184);
+        assert_eq!(calculator.calculate_levelup_require_xp(189), 22_143_732);
+        assert_eq!(calculator.calculate_levelup_require_xp(190), 36_400_000);
+    }
+
+    #[test]
+    fn levelup_require_xp_does_not_overflow_above_level_190() {
+        let calculator = create_calculator();
+
+        // Exceeds u32::MAX, which previously overflowed before the conversion to u64
+        assert_eq!(calculator.calculate_levelup_require_xp(250), 19_601_920_000);
+
+        let mut previous_xp = calculator.calculate_levelup_require_xp(190);
+        for level in 191..=300 {
+            let xp = calculator.calculate_levelup_require_xp(level);
+            assert!(xp > previous_xp, "level {} xp did not increase", level);
+            previous_xp = xp;
+        }
+    }
+}
@@ -2,19 +2,25 @@ use log::{debug, warn};
 use std::collections::HashMap;
 
 use crate::data::{
-    formats::{AipFile, FileReader, StbFile, VfsIndex},
+    formats::{AipFile, FileReader, StbFile, StlFile, VfsIndex},
     AiDatabase,
 };
 
 pub fn get_ai_database(vfs: &VfsIndex) -> Option<AiDatabase> {
     let ai_s = vfs.open_file("3DDATA/AI/AI_s.STB")?;
     let ai_s_stb = StbFile::read_wide(FileReader::from(&ai_s)).ok()?;
+    let ai_stl = vfs
+        .open_file("3DDATA/AI/ULNGTB_AI.STL")
+        .and_then(|file| StlFile::read(FileReader::from(&file)).ok());
     let mut strings = HashMap::new();
 
     for row in 0..ai_s_stb.rows() {
-        let english = ai_s_stb.get(row, 1);
-        if !english.is_empty() {
-            strings.insert(row as u16, english.to_string());
+        let text = ai_stl
+            .as_ref()
+            .and_then(|stl| stl.get_text_string(1, ai_s_stb.get(row, 0)))
+            .unwrap_or_else(|| ai_s_stb.get(row, 1));
+        if !text.is_empty() {
+            strings.insert(row as u16, text.to_string());
         }
     }
 
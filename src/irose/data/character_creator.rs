@@ -10,9 +10,9 @@ use crate::{
         ItemReference, SkillDatabase, SkillId, ZoneDatabase, ZoneId,
     },
     game::components::{
-        BasicStats, CharacterInfo, Equipment, ExperiencePoints, HealthPoints, Hotbar, Inventory,
-        Level, ManaPoints, Position, QuestState, SkillList, SkillPoints, Stamina, StatPoints,
-        UnionMembership,
+        BasicStats, CharacterInfo, Equipment, ExperiencePoints, FriendList, HealthPoints, Hotbar,
+        Inventory, Level, Mailbox, ManaPoints, Position, QuestState, SavedCooldowns, SkillList,
+        SkillPoints, Stamina, StatPoints, UnionMembership,
     },
     stb_column,
 };
@@ -163,6 +163,9 @@ impl CharacterCreator for CharacterCreatorData {
             quest_state: QuestState::new(),
             union_membership: UnionMembership::new(),
             stamina: Stamina::new(),
+            friend_list: FriendList::new(),
+            mailbox: Mailbox::new(),
+            cooldowns: SavedCooldowns::default(),
         };
 
         for &skill_id in &self.skills {
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use arrayvec::ArrayVec;
+use log::debug;
+
+use crate::{
+    data::{
+        formats::{FileReader, StbFile, VfsIndex},
+        JobClassData, JobClassDatabase, JobClassId,
+    },
+    stb_column,
+};
+
+struct StbJobClass(StbFile);
+
+impl StbJobClass {
+    stb_column! { 1..=8, get_jobs, ArrayVec<u16, 8> }
+}
+
+fn load_job_class(data: &StbJobClass, id: usize) -> Option<JobClassData> {
+    let jobs = data.get_jobs(id);
+    if jobs.is_empty() {
+        return None;
+    }
+
+    Some(JobClassData {
+        id: JobClassId::new(id)?,
+        jobs,
+    })
+}
+
+pub fn get_job_class_database(vfs: &VfsIndex) -> Option<JobClassDatabase> {
+    let file = vfs.open_file("3DDATA/STB/LIST_CLASS.STB")?;
+    let data = StbJobClass(StbFile::read(FileReader::from(&file)).ok()?);
+    let mut job_classes = HashMap::new();
+
+    for row in 1..data.0.rows() {
+        if let Some(job_class_data) = load_job_class(&data, row) {
+            job_classes.insert(row, job_class_data);
+        }
+    }
+
+    debug!("Loaded {} job classes", job_classes.len());
+    Some(JobClassDatabase::new(job_classes))
+}
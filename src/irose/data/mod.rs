@@ -18,6 +18,7 @@ mod ai_database;
 mod character_creator;
 mod drop_table;
 mod item_database;
+mod job_class_database;
 mod motion_database;
 mod npc_database;
 mod quest_database;
@@ -30,6 +31,7 @@ use ai_database::get_ai_database;
 use character_creator::get_character_creator;
 use drop_table::get_drop_table;
 use item_database::get_item_database;
+use job_class_database::get_job_class_database;
 use motion_database::get_motion_database;
 use npc_database::get_npc_database;
 use num_traits::FromPrimitive;
@@ -69,6 +71,9 @@ pub fn get_game_data() -> GameData {
         drop_table,
         ai: Arc::new(get_ai_database(&vfs_index).expect("Failed to load AI database")),
         items: item_database,
+        job_classes: Arc::new(
+            get_job_class_database(&vfs_index).expect("Failed to load job class database"),
+        ),
         motions: Arc::new(get_motion_database(&vfs_index).expect("Failed to load motion database")),
         npcs: npc_database,
         quests: Arc::new(get_quest_database(&vfs_index).expect("Failed to load quest database")),
@@ -38,7 +38,7 @@ impl StbStatus {
     stb_column! { 1, get_status_effect_type, StatusEffectType }
     stb_column! { 2, get_can_be_reapplied, bool }
     stb_column! { 3, get_cleared_by_type, StatusEffectClearedByType }
-    stb_column! { 4, get_apply_arg, i32 }
+    stb_column! { 4, get_apply_per_second_value, i32 }
     stb_column! { (5..=8).step_by(2), get_apply_status_effect_id, [Option<StatusEffectId>; 2] }
     stb_column! { (5..=8).step_by(2), get_apply_status_effect_value, [Option<i32>; 2] }
 
@@ -79,6 +79,7 @@ fn load_status_effect(data: &StbStatus, stl: &StlFile, row: usize) -> Option<Sta
         cleared_by_type: data
             .get_cleared_by_type(row)
             .unwrap_or(StatusEffectClearedByType::ClearGood),
+        apply_per_second_value: data.get_apply_per_second_value(row).unwrap_or(0),
         apply_status_effects: data.get_apply_status_effects(row),
     })
 }
@@ -296,6 +296,7 @@ fn load_zone(
         night_time: data
             .get_zone_night_time(id)
             .unwrap_or((5 * WORLD_TICKS_PER_DAY / 6) as u32),
+        planet: data.get_zone_planet(id).unwrap_or(0),
     })
 }
 
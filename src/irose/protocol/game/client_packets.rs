@@ -33,6 +33,8 @@ pub enum ClientPackets {
     ReviveRequest = 0x755,
     Emote = 0x781,
     Chat = 0x783,
+    Whisper = 0x784,
+    ShoutChat = 0x785,
     StopMove = 0x796,
     Attack = 0x798,
     Move = 0x79a,
@@ -50,6 +52,9 @@ pub enum ClientPackets {
     PersonalStoreListItems = 0x7c4,
     PersonalStoreBuyItem = 0x7c5,
     MoveToggle = 0x782,
+    Messenger = 0x7e1,
+    MessengerChat = 0x7e2,
+    Memo = 0x7e5,
 }
 
 #[derive(Debug)]
@@ -169,6 +174,46 @@ impl<'a> TryFrom<&'a Packet> for PacketClientChat<'a> {
     }
 }
 
+#[derive(Debug)]
+pub struct PacketClientWhisper<'a> {
+    pub target: &'a str,
+    pub text: &'a str,
+}
+
+impl<'a> TryFrom<&'a Packet> for PacketClientWhisper<'a> {
+    type Error = ProtocolError;
+
+    fn try_from(packet: &'a Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::Whisper as u16 {
+            return Err(ProtocolError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let target = reader.read_null_terminated_utf8()?;
+        let text = reader.read_null_terminated_utf8()?;
+        Ok(PacketClientWhisper { target, text })
+    }
+}
+
+#[derive(Debug)]
+pub struct PacketClientShoutChat<'a> {
+    pub text: &'a str,
+}
+
+impl<'a> TryFrom<&'a Packet> for PacketClientShoutChat<'a> {
+    type Error = ProtocolError;
+
+    fn try_from(packet: &'a Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::ShoutChat as u16 {
+            return Err(ProtocolError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let text = reader.read_null_terminated_utf8()?;
+        Ok(PacketClientShoutChat { text })
+    }
+}
+
 #[derive(Debug)]
 pub struct PacketClientSetHotbarSlot {
     pub slot_index: u8,
@@ -638,3 +683,91 @@ impl TryFrom<&Packet> for PacketClientEmote {
         Ok(PacketClientEmote { motion_id, is_stop })
     }
 }
+
+#[derive(Debug)]
+pub enum PacketClientMessenger<'a> {
+    AddFriendRequest(&'a str),
+    AddFriendAccept(&'a str),
+    AddFriendReject(&'a str),
+    DeleteFriend(u32),
+    ToggleBlockFriend(u32),
+}
+
+impl<'a> TryFrom<&'a Packet> for PacketClientMessenger<'a> {
+    type Error = ProtocolError;
+
+    fn try_from(packet: &'a Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::Messenger as u16 {
+            return Err(ProtocolError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        match reader.read_u8()? {
+            0x01 => Ok(PacketClientMessenger::AddFriendRequest(
+                reader.read_null_terminated_utf8()?,
+            )),
+            0x02 => {
+                let _requester_entity_id = reader.read_u16()?;
+                Ok(PacketClientMessenger::AddFriendAccept(
+                    reader.read_null_terminated_utf8()?,
+                ))
+            }
+            0x03 => Ok(PacketClientMessenger::AddFriendReject(
+                reader.read_null_terminated_utf8()?,
+            )),
+            0x07 => Ok(PacketClientMessenger::DeleteFriend(reader.read_u32()?)),
+            0x08 => Ok(PacketClientMessenger::ToggleBlockFriend(reader.read_u32()?)),
+            _ => Err(ProtocolError::InvalidPacket),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PacketClientMessengerChat<'a> {
+    pub tag: u32,
+    pub text: &'a str,
+}
+
+impl<'a> TryFrom<&'a Packet> for PacketClientMessengerChat<'a> {
+    type Error = ProtocolError;
+
+    fn try_from(packet: &'a Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::MessengerChat as u16 {
+            return Err(ProtocolError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        let tag = reader.read_u32()?;
+        let text = reader.read_null_terminated_utf8()?;
+        Ok(PacketClientMessengerChat { tag, text })
+    }
+}
+
+#[derive(Debug)]
+pub enum PacketClientMemo<'a> {
+    RequestContents,
+    Send { recipient: &'a str, text: &'a str },
+    RequestReceivedCount,
+}
+
+impl<'a> TryFrom<&'a Packet> for PacketClientMemo<'a> {
+    type Error = ProtocolError;
+
+    fn try_from(packet: &'a Packet) -> Result<Self, Self::Error> {
+        if packet.command != ClientPackets::Memo as u16 {
+            return Err(ProtocolError::InvalidPacket);
+        }
+
+        let mut reader = PacketReader::from(packet);
+        match reader.read_u8()? {
+            0x01 => Ok(PacketClientMemo::RequestContents),
+            0x02 => {
+                let recipient = reader.read_null_terminated_utf8()?;
+                let text = reader.read_null_terminated_utf8()?;
+                Ok(PacketClientMemo::Send { recipient, text })
+            }
+            0x03 => Ok(PacketClientMemo::RequestReceivedCount),
+            _ => Err(ProtocolError::InvalidPacket),
+        }
+    }
+}
@@ -14,8 +14,8 @@ use crate::{
         },
         server::{
             AnnounceChat, ApplySkillEffect, CastSkillSelf, CastSkillTargetEntity,
-            CastSkillTargetPosition, LocalChat, LogoutReply, MoveToggle, OpenPersonalStore,
-            PersonalStoreTransactionCancelled, PersonalStoreTransactionResult,
+            CastSkillTargetPosition, LocalChat, LogoutReply, MessengerChat, MoveToggle,
+            OpenPersonalStore, PersonalStoreTransactionCancelled, PersonalStoreTransactionResult,
             PersonalStoreTransactionSoldOut, PersonalStoreTransactionSuccess,
             PickupDroppedItemResult, QuestDeleteResult, QuestTriggerResult, RemoveEntities,
             ServerMessage, ShoutChat, SpawnEntityDroppedItem, SpawnEntityMonster, SpawnEntityNpc,
@@ -136,6 +136,7 @@ impl GameClient {
                         health_points: &response.health_points,
                         mana_points: &response.mana_points,
                         world_ticks: response.world_ticks,
+                        is_pvp_enabled: response.is_pvp_enabled,
                     }))
                     .await?;
             }
@@ -145,6 +146,19 @@ impl GameClient {
                     .client_message_tx
                     .send(ClientMessage::Chat(String::from(packet.text)))?;
             }
+            Some(ClientPackets::Whisper) => {
+                let packet = PacketClientWhisper::try_from(&packet)?;
+                client.client_message_tx.send(ClientMessage::Whisper(
+                    String::from(packet.target),
+                    String::from(packet.text),
+                ))?;
+            }
+            Some(ClientPackets::ShoutChat) => {
+                let packet = PacketClientShoutChat::try_from(&packet)?;
+                client
+                    .client_message_tx
+                    .send(ClientMessage::ShoutChat(String::from(packet.text)))?;
+            }
             Some(ClientPackets::Move) => {
                 let packet = PacketClientMove::try_from(&packet)?;
                 client.client_message_tx.send(ClientMessage::Move(Move {
@@ -346,6 +360,50 @@ impl GameClient {
                     .client_message_tx
                     .send(ClientMessage::UseEmote(packet.motion_id, packet.is_stop))?;
             }
+            Some(ClientPackets::Messenger) => {
+                let message = match PacketClientMessenger::try_from(&packet)? {
+                    PacketClientMessenger::AddFriendRequest(name) => {
+                        ClientMessage::MessengerAddFriend(String::from(name))
+                    }
+                    PacketClientMessenger::AddFriendAccept(name) => {
+                        ClientMessage::MessengerAddFriendAccept(String::from(name))
+                    }
+                    PacketClientMessenger::AddFriendReject(name) => {
+                        ClientMessage::MessengerAddFriendReject(String::from(name))
+                    }
+                    PacketClientMessenger::DeleteFriend(tag) => {
+                        ClientMessage::MessengerDeleteFriend(tag)
+                    }
+                    PacketClientMessenger::ToggleBlockFriend(tag) => {
+                        ClientMessage::MessengerToggleBlockFriend(tag)
+                    }
+                };
+                client.client_message_tx.send(message)?;
+            }
+            Some(ClientPackets::MessengerChat) => {
+                let packet = PacketClientMessengerChat::try_from(&packet)?;
+                client.client_message_tx.send(ClientMessage::MessengerChat(
+                    packet.tag,
+                    String::from(packet.text),
+                ))?;
+            }
+            Some(ClientPackets::Memo) => match PacketClientMemo::try_from(&packet)? {
+                PacketClientMemo::Send { recipient, text } => {
+                    client.client_message_tx.send(ClientMessage::SendMail(
+                        String::from(recipient),
+                        String::from(text),
+                    ))?;
+                }
+                // Mail is delivered as soon as the character joins the zone, so there
+                // is never any mail stored for the client to request.
+                PacketClientMemo::RequestContents | PacketClientMemo::RequestReceivedCount => {
+                    warn!(
+                        "[GS] Unhandled memo packet [{:#03X}] {:02x?}",
+                        packet.command,
+                        &packet.data[..]
+                    )
+                }
+            },
             _ => warn!(
                 "[GS] Unhandled packet [{:#03X}] {:02x?}",
                 packet.command,
@@ -789,6 +847,12 @@ impl GameClient {
                     .write_packet(Packet::from(&PacketServerRunNpcDeathTrigger { npc_id }))
                     .await?;
             }
+            ServerMessage::ChangeNpcId(entity_id, npc_id) => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerChangeNpcId { entity_id, npc_id }))
+                    .await?;
+            }
             ServerMessage::OpenPersonalStore(OpenPersonalStore {
                 entity_id,
                 skin,
@@ -1022,6 +1086,66 @@ impl GameClient {
                     }))
                     .await?;
             }
+            ServerMessage::MessengerFriendList(ref friends) => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerMessenger::FriendList(friends)))
+                    .await?;
+            }
+            ServerMessage::MessengerAddFriendRequest(requester_entity_id, ref requester_name) => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerMessenger::AddFriendRequest {
+                        requester_entity_id,
+                        requester_name,
+                    }))
+                    .await?;
+            }
+            ServerMessage::MessengerAddFriendAccepted(ref friend) => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerMessenger::AddFriendAccepted(
+                        friend,
+                    )))
+                    .await?;
+            }
+            ServerMessage::MessengerAddFriendRejected(ref name) => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerMessenger::AddFriendRejected(
+                        name,
+                    )))
+                    .await?;
+            }
+            ServerMessage::MessengerAddFriendNotFound(ref name) => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerMessenger::AddFriendNotFound(
+                        name,
+                    )))
+                    .await?;
+            }
+            ServerMessage::MessengerFriendStatus(tag, status) => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerMessenger::FriendStatus {
+                        tag,
+                        status,
+                    }))
+                    .await?;
+            }
+            ServerMessage::MessengerChat(MessengerChat { tag, ref text }) => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerMessengerChat { tag, text }))
+                    .await?;
+            }
+            ServerMessage::UpdateZonePvpEnabled(is_pvp_enabled) => {
+                client
+                    .connection
+                    .write_packet(Packet::from(&PacketServerSetGlobalFlags { is_pvp_enabled }))
+                    .await?;
+            }
             // These messages are for World Server
             ServerMessage::ReturnToCharacterSelect => {
                 panic!("Received unexpected server message for game server")
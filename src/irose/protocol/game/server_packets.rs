@@ -19,8 +19,9 @@ use crate::{
             StatPoints, StatusEffects, Team, UnionMembership, VehiclePartIndex,
         },
         messages::server::{
-            CancelCastingSkillReason, LearnSkillError, LearnSkillSuccess, NpcStoreTransactionError,
-            PickupDroppedItemContent, PickupDroppedItemError,
+            CancelCastingSkillReason, LearnSkillError, LearnSkillSuccess, MessengerFriend,
+            MessengerFriendStatus, NpcStoreTransactionError, PickupDroppedItemContent,
+            PickupDroppedItemError,
         },
     },
     irose::protocol::game::common_packets::{
@@ -49,6 +50,7 @@ pub enum ServerPackets {
     QuestResult = 0x730,
     RunNpcDeathTrigger = 0x731,
     JoinZone = 0x753,
+    ChangeNpcId = 0x774,
     UseEmote = 0x781,
     LocalChat = 0x783,
     Whisper = 0x784,
@@ -64,6 +66,7 @@ pub enum ServerPackets {
     MoveEntity = 0x79a,
     UpdateXpStamina = 0x79b,
     UpdateLevel = 0x79e,
+    NpcStoreTransactionError = 0x7a1,
     UseItem = 0x7a3,
     UpdateEquipment = 0x7a5,
     SpawnEntityDroppedItem = 0x7a6,
@@ -88,6 +91,9 @@ pub enum ServerPackets {
     PersonalStoreTransactionResult = 0x7c6,
     PersonalStoreTransactionUpdateMoneyAndInventory = 0x7c7,
     MoveToggle = 0x782,
+    SetGlobalFlags = 0x7d7,
+    Messenger = 0x7e1,
+    MessengerChat = 0x7e2,
 }
 
 #[allow(dead_code)]
@@ -472,6 +478,7 @@ pub struct PacketServerJoinZone<'a> {
     pub health_points: &'a HealthPoints,
     pub mana_points: &'a ManaPoints,
     pub world_ticks: WorldTicks,
+    pub is_pvp_enabled: bool,
 }
 
 impl<'a> From<&'a PacketServerJoinZone<'a>> for Packet {
@@ -492,7 +499,7 @@ impl<'a> From<&'a PacketServerJoinZone<'a>> for Packet {
         for _ in 0..11 {
             writer.write_u8(100); // item rate
         }
-        writer.write_u32(0); // global flags (0x1 = pvp allowed)
+        writer.write_u32(if packet.is_pvp_enabled { 0x1 } else { 0 }); // global flags
 
         writer.write_u32(packet.world_ticks.0 as u32);
         writer.write_u32(packet.team.id);
@@ -1141,6 +1148,11 @@ impl From<&PacketServerLearnSkillResult> for Packet {
                     LearnSkillError::JobRequirement => writer.write_u8(2),
                     LearnSkillError::SkillRequirement => writer.write_u8(3),
                     LearnSkillError::AbilityRequirement => writer.write_u8(4),
+                    // The client has no specific result for union or planet requirements,
+                    // so use the generic failed result
+                    LearnSkillError::UnionRequirement | LearnSkillError::PlanetRequirement => {
+                        writer.write_u8(0)
+                    }
                     LearnSkillError::Full => writer.write_u8(5),
                     LearnSkillError::InvalidSkillId => writer.write_u8(6),
                     LearnSkillError::SkillPointRequirement => writer.write_u8(7),
@@ -1447,6 +1459,9 @@ impl From<&PacketServerCancelCastingSkill> for Packet {
             CancelCastingSkillReason::NeedAbility => writer.write_u8(1),
             CancelCastingSkillReason::NeedTarget => writer.write_u8(2),
             CancelCastingSkillReason::InvalidTarget => writer.write_u8(3),
+            // Interrupted is not a reason the original server sends, use the next value
+            // so it can still be told apart from an invalid target
+            CancelCastingSkillReason::Interrupted => writer.write_u8(4),
         }
         writer.into()
     }
@@ -1513,7 +1528,7 @@ pub struct PacketServerNpcStoreTransactionError {
 }
 impl From<&PacketServerNpcStoreTransactionError> for Packet {
     fn from(packet: &PacketServerNpcStoreTransactionError) -> Self {
-        let mut writer = PacketWriter::new(ServerPackets::UpdateSpeed as u16);
+        let mut writer = PacketWriter::new(ServerPackets::NpcStoreTransactionError as u16);
 
         let error = match packet.error {
             NpcStoreTransactionError::PriceDifference => 1,
@@ -1522,7 +1537,11 @@ impl From<&PacketServerNpcStoreTransactionError> for Packet {
             NpcStoreTransactionError::NotEnoughMoney => 4,
             NpcStoreTransactionError::NotSameUnion => 5,
             NpcStoreTransactionError::NotEnoughUnionPoints => 6,
-            _ => panic!("Unexpected PacketServerNpcStoreTransactionError"),
+            // The client has no specific result for these, so use the closest match
+            NpcStoreTransactionError::InvalidTransactionEntity
+            | NpcStoreTransactionError::InvalidItem
+            | NpcStoreTransactionError::InventoryFull => 1,
+            NpcStoreTransactionError::MoneyOverflow => 4,
         };
 
         writer.write_u8(error);
@@ -1548,6 +1567,20 @@ impl From<&PacketServerMoveToggle> for Packet {
     }
 }
 
+pub struct PacketServerChangeNpcId {
+    pub entity_id: ClientEntityId,
+    pub npc_id: NpcId,
+}
+
+impl From<&PacketServerChangeNpcId> for Packet {
+    fn from(packet: &PacketServerChangeNpcId) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::ChangeNpcId as u16);
+        writer.write_entity_id(packet.entity_id);
+        writer.write_u16(packet.npc_id.get() as u16);
+        writer.into()
+    }
+}
+
 pub struct PacketServerSitToggle {
     pub entity_id: ClientEntityId,
 }
@@ -1576,3 +1609,98 @@ impl From<&PacketServerUseEmote> for Packet {
         writer.into()
     }
 }
+
+fn encode_messenger_friend_status(status: MessengerFriendStatus) -> u8 {
+    match status {
+        MessengerFriendStatus::Online => 0x07,
+        MessengerFriendStatus::Blocked => 0x08,
+        MessengerFriendStatus::Offline => 0x0a,
+        MessengerFriendStatus::Deleted => 0x0b,
+    }
+}
+
+pub enum PacketServerMessenger<'a> {
+    AddFriendRequest {
+        requester_entity_id: ClientEntityId,
+        requester_name: &'a str,
+    },
+    AddFriendAccepted(&'a MessengerFriend),
+    AddFriendRejected(&'a str),
+    AddFriendNotFound(&'a str),
+    FriendStatus {
+        tag: u32,
+        status: MessengerFriendStatus,
+    },
+    FriendList(&'a [MessengerFriend]),
+}
+
+impl<'a> From<&'a PacketServerMessenger<'a>> for Packet {
+    fn from(packet: &'a PacketServerMessenger<'a>) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::Messenger as u16);
+        match *packet {
+            PacketServerMessenger::AddFriendRequest {
+                requester_entity_id,
+                requester_name,
+            } => {
+                writer.write_u8(0x01);
+                writer.write_entity_id(requester_entity_id);
+                writer.write_null_terminated_utf8(requester_name);
+            }
+            PacketServerMessenger::AddFriendAccepted(friend) => {
+                writer.write_u8(0x02);
+                writer.write_u32(friend.tag);
+                writer.write_u8(encode_messenger_friend_status(friend.status));
+                writer.write_null_terminated_utf8(&friend.name);
+            }
+            PacketServerMessenger::AddFriendRejected(name) => {
+                writer.write_u8(0x03);
+                writer.write_null_terminated_utf8(name);
+            }
+            PacketServerMessenger::AddFriendNotFound(name) => {
+                writer.write_u8(0x04);
+                writer.write_null_terminated_utf8(name);
+            }
+            PacketServerMessenger::FriendStatus { tag, status } => {
+                writer.write_u8(0x05);
+                writer.write_u32(tag);
+                writer.write_u8(encode_messenger_friend_status(status));
+            }
+            PacketServerMessenger::FriendList(friends) => {
+                writer.write_u8(0x06);
+                writer.write_u8(friends.len() as u8);
+                for friend in friends {
+                    writer.write_u32(friend.tag);
+                    writer.write_u8(encode_messenger_friend_status(friend.status));
+                    writer.write_null_terminated_utf8(&friend.name);
+                }
+            }
+        }
+        writer.into()
+    }
+}
+
+pub struct PacketServerMessengerChat<'a> {
+    pub tag: u32,
+    pub text: &'a str,
+}
+
+impl<'a> From<&'a PacketServerMessengerChat<'a>> for Packet {
+    fn from(packet: &'a PacketServerMessengerChat<'a>) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::MessengerChat as u16);
+        writer.write_u32(packet.tag);
+        writer.write_null_terminated_utf8(packet.text);
+        writer.into()
+    }
+}
+
+pub struct PacketServerSetGlobalFlags {
+    pub is_pvp_enabled: bool,
+}
+
+impl From<&PacketServerSetGlobalFlags> for Packet {
+    fn from(packet: &PacketServerSetGlobalFlags) -> Self {
+        let mut writer = PacketWriter::new(ServerPackets::SetGlobalFlags as u16);
+        writer.write_u32(if packet.is_pvp_enabled { 0x1 } else { 0 });
+        writer.into()
+    }
+}
@@ -9,12 +9,121 @@ mod game;
 mod irose;
 mod protocol;
 
+use clap::{App, Arg};
 use log::debug;
 use simplelog::*;
-use std::time::Instant;
+use std::{path::Path, time::Instant};
 use tokio::net::TcpListener;
 
-use crate::protocol::server::{GameServer, LoginServer, WorldServer};
+use crate::{
+    data::{
+        formats::{qsd::QsdFile, AipFile, FileReader, StbFile, VfsIndex},
+        NpcId,
+    },
+    game::{
+        is_quest_condition_implemented, is_quest_reward_implemented, GameConfig,
+        HitStunDamageSource,
+    },
+    protocol::server::{GameServer, LoginServer, WorldServer},
+};
+
+fn dump_aip(path: &str) {
+    let vfs_index = VfsIndex::load(Path::new("data.idx")).expect("Failed reading data.idx");
+    let file = vfs_index
+        .open_file(path)
+        .unwrap_or_else(|| panic!("Could not find {} in data.idx", path));
+
+    match AipFile::read(FileReader::from(&file)) {
+        Ok(aip) => print!("{}", aip),
+        Err(error) => println!("Failed to parse {}, error: {:?}", path, error),
+    }
+}
+
+fn dump_qsd(path: Option<&str>, validate_only: bool) {
+    let vfs_index = VfsIndex::load(Path::new("data.idx")).expect("Failed reading data.idx");
+    let qsd_paths = if let Some(path) = path {
+        vec![path.to_string()]
+    } else {
+        let file = vfs_index
+            .open_file("3DDATA/STB/LIST_QUESTDATA.STB")
+            .expect("Could not find LIST_QUESTDATA.STB in data.idx");
+        let qsd_files_stb =
+            StbFile::read(FileReader::from(&file)).expect("Failed to parse LIST_QUESTDATA.STB");
+        (0..qsd_files_stb.rows())
+            .map(|row| qsd_files_stb.get(row, 0).to_string())
+            .filter(|qsd_path| !qsd_path.is_empty())
+            .collect()
+    };
+
+    let mut num_triggers = 0;
+    let mut num_unimplemented_triggers = 0;
+
+    for qsd_path in qsd_paths.iter() {
+        let qsd = match vfs_index
+            .open_file(qsd_path)
+            .map(|file| QsdFile::read(FileReader::from(&file)))
+        {
+            Some(Ok(qsd)) => qsd,
+            Some(Err(error)) => {
+                println!("Failed to parse {}, error: {:?}", qsd_path, error);
+                continue;
+            }
+            None => {
+                println!("Could not find {} in data.idx", qsd_path);
+                continue;
+            }
+        };
+
+        let mut triggers: Vec<_> = qsd.triggers.values().collect();
+        triggers.sort_by(|a, b| a.name.cmp(&b.name));
+
+        if !validate_only {
+            println!("// {}", qsd_path);
+        }
+
+        for trigger in triggers {
+            num_triggers += 1;
+
+            if !validate_only {
+                println!("{}", trigger);
+            }
+
+            let mut is_implemented = true;
+            for condition in trigger
+                .conditions
+                .iter()
+                .filter(|condition| !is_quest_condition_implemented(condition))
+            {
+                println!(
+                    "UNIMPLEMENTED {} \"{}\" condition {:?}",
+                    qsd_path, trigger.name, condition
+                );
+                is_implemented = false;
+            }
+
+            for reward in trigger
+                .rewards
+                .iter()
+                .filter(|reward| !is_quest_reward_implemented(reward))
+            {
+                println!(
+                    "UNIMPLEMENTED {} \"{}\" reward {:?}",
+                    qsd_path, trigger.name, reward
+                );
+                is_implemented = false;
+            }
+
+            if !is_implemented {
+                num_unimplemented_triggers += 1;
+            }
+        }
+    }
+
+    println!(
+        "{} of {} triggers use unimplemented conditions or rewards",
+        num_unimplemented_triggers, num_triggers
+    );
+}
 
 #[tokio::main]
 async fn main() {
@@ -26,13 +135,115 @@ async fn main() {
     )
     .expect("Failed to initialise logging");
 
+    let matches = App::new("rose-offline")
+        .arg(
+            Arg::new("max-character-level")
+                .long("max-character-level")
+                .takes_value(true)
+                .about("Maximum level a character can reach"),
+        )
+        .arg(
+            Arg::new("death-xp-penalty-percent")
+                .long("death-xp-penalty-percent")
+                .takes_value(true)
+                .about("Percentage of the level's required xp lost on death"),
+        )
+        .arg(
+            Arg::new("revive-position-xp-penalty-percent")
+                .long("revive-position-xp-penalty-percent")
+                .takes_value(true)
+                .about("Additional percentage of xp lost reviving at the nearest revive point"),
+        )
+        .arg(
+            Arg::new("disable-attack-hit-stun")
+                .long("disable-attack-hit-stun")
+                .about("Normal attacks do not interrupt movement or casting"),
+        )
+        .arg(
+            Arg::new("disable-skill-hit-stun")
+                .long("disable-skill-hit-stun")
+                .about("Skill damage does not interrupt movement or casting"),
+        )
+        .arg(
+            Arg::new("hit-stun-immune-npc")
+                .long("hit-stun-immune-npc")
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .about("NPC id which is never interrupted by hit stun, can be repeated"),
+        )
+        .subcommand(
+            App::new("dump-aip")
+                .about("Print an AI file from data.idx as readable pseudo-code")
+                .arg(Arg::new("path").required(true)),
+        )
+        .subcommand(
+            App::new("dump-qsd")
+                .about("Print quest triggers from data.idx and report unimplemented ones")
+                .arg(Arg::new("path"))
+                .arg(Arg::new("validate").long("validate")),
+        )
+        .get_matches();
+
+    if let Some(arg_matches) = matches.subcommand_matches("dump-aip") {
+        dump_aip(arg_matches.value_of("path").unwrap());
+        return;
+    }
+
+    if let Some(arg_matches) = matches.subcommand_matches("dump-qsd") {
+        dump_qsd(
+            arg_matches.value_of("path"),
+            arg_matches.is_present("validate"),
+        );
+        return;
+    }
+
+    let mut game_config = GameConfig::new();
+    if let Some(max_character_level) = matches.value_of("max-character-level") {
+        game_config.max_character_level = max_character_level
+            .parse()
+            .expect("Invalid value for max-character-level");
+    }
+    if let Some(death_xp_penalty_percent) = matches.value_of("death-xp-penalty-percent") {
+        game_config.death_xp_penalty_percent = death_xp_penalty_percent
+            .parse()
+            .expect("Invalid value for death-xp-penalty-percent");
+    }
+    if let Some(revive_position_xp_penalty_percent) =
+        matches.value_of("revive-position-xp-penalty-percent")
+    {
+        game_config.revive_position_xp_penalty_percent = revive_position_xp_penalty_percent
+            .parse()
+            .expect("Invalid value for revive-position-xp-penalty-percent");
+    }
+    if matches.is_present("disable-attack-hit-stun") {
+        game_config
+            .hit_stun_damage_sources
+            .remove(&HitStunDamageSource::Attack);
+    }
+    if matches.is_present("disable-skill-hit-stun") {
+        game_config
+            .hit_stun_damage_sources
+            .remove(&HitStunDamageSource::Skill);
+    }
+    if let Some(hit_stun_immune_npcs) = matches.values_of("hit-stun-immune-npc") {
+        for npc_id in hit_stun_immune_npcs {
+            game_config.hit_stun_immune_npc_ids.insert(
+                npc_id
+                    .parse()
+                    .ok()
+                    .and_then(NpcId::new)
+                    .expect("Invalid value for hit-stun-immune-npc"),
+            );
+        }
+    }
+
     let started_load = Instant::now();
     let game_data = irose::get_game_data();
     debug!("Time take to read game data {:?}", started_load.elapsed());
 
     let (game_control_tx, game_control_rx) = crossbeam_channel::unbounded();
     std::thread::spawn(move || {
-        game::GameWorld::new(game_control_rx).run(game_data);
+        game::GameWorld::new(game_control_rx).run(game_data, game_config);
     });
 
     let mut login_server = LoginServer::new(